@@ -0,0 +1,243 @@
+use std::collections::BTreeSet;
+use std::hash::Hash;
+
+use pdf_writer::{Content, Finish, Name, Pdf, Rect, Ref, Str};
+use qrcodegen::{QrCode, QrCodeEcc};
+use serde::Serialize;
+
+use cube_shuffle_core::distribution_shuffle::Pack;
+
+// A4 in points with two columns of five labels.
+const PAGE_WIDTH: f32 = 595.0;
+const PAGE_HEIGHT: f32 = 842.0;
+const MARGIN: f32 = 36.0;
+const COLUMNS: usize = 2;
+const ROWS: usize = 5;
+const PADDING: f32 = 10.0;
+const QR_SIZE: f32 = 72.0;
+const FONT: Name = Name(b"F1");
+// Widths of the printable ASCII characters in Helvetica, in thousandths of the font size.
+const WIDTHS: [u16; 95] = [
+    278, 278, 355, 556, 556, 889, 667, 222, 333, 333, 389, 584, 278, 333, 278, 278, 556, 556, 556,
+    556, 556, 556, 556, 556, 556, 556, 278, 278, 584, 584, 584, 556, 1015, 667, 667, 722, 722, 667,
+    611, 778, 722, 278, 500, 667, 556, 833, 722, 778, 667, 778, 722, 667, 611, 722, 667, 944, 667,
+    667, 611, 278, 278, 278, 469, 556, 222, 556, 556, 500, 556, 556, 278, 556, 556, 222, 222, 500,
+    222, 833, 556, 556, 556, 556, 333, 500, 278, 556, 500, 722, 500, 500, 500, 334, 260, 334, 584,
+];
+
+// Helvetica uses the standard encoding, which only covers ASCII reliably.
+fn ascii(text: &str) -> Vec<u8> {
+    text.chars()
+        .map(|c| {
+            if c.is_ascii() && !c.is_ascii_control() {
+                c as u8
+            } else {
+                b'?'
+            }
+        })
+        .collect()
+}
+
+fn text_width(text: &[u8], font_size: f32) -> f32 {
+    text.iter()
+        .map(|c| WIDTHS[(*c - b' ') as usize] as f32 * font_size / 1000.0)
+        .sum()
+}
+
+// Cuts long pile names short with "...", so they do not run into the counts.
+fn fit(text: &[u8], max_width: f32, font_size: f32) -> Vec<u8> {
+    if text_width(text, font_size) <= max_width {
+        return text.to_vec();
+    }
+    let mut fitted = text.to_vec();
+    while !fitted.is_empty()
+        && text_width(&fitted, font_size) + text_width(b"...", font_size) > max_width
+    {
+        fitted.pop();
+    }
+    while fitted.last() == Some(&b' ') {
+        fitted.pop();
+    }
+    fitted.extend_from_slice(b"...");
+    fitted
+}
+
+fn qr_code(content: &mut Content, text: &str, x: f32, y: f32) {
+    let qr = QrCode::encode_text(text, QrCodeEcc::Low).unwrap();
+    let module = QR_SIZE / qr.size() as f32;
+    for row in 0..qr.size() {
+        for column in 0..qr.size() {
+            if qr.get_module(column, row) {
+                content.rect(
+                    x + column as f32 * module,
+                    y + QR_SIZE - (row + 1) as f32 * module,
+                    module,
+                    module,
+                );
+            }
+        }
+    }
+    content.fill_nonzero();
+}
+
+fn label<P>(
+    content: &mut Content,
+    pack: &Pack<P>,
+    pile_names: &[&str],
+    number: usize,
+    seed: u64,
+    x: f32,
+    y: f32,
+) where
+    P: AsRef<str> + Hash + Eq + Serialize,
+{
+    let width = (PAGE_WIDTH - 2.0 * MARGIN) / COLUMNS as f32;
+    let height = (PAGE_HEIGHT - 2.0 * MARGIN) / ROWS as f32;
+    content.set_line_width(0.5);
+    content.rect(x, y, width, height);
+    content.stroke();
+
+    let top = y + height - PADDING;
+    content.begin_text();
+    content.set_font(FONT, 16.0);
+    content.next_line(x + PADDING, top - 16.0);
+    content.show(Str(format!("Pack {}", number).as_bytes()));
+    content.end_text();
+
+    let lines_height = height - 2.0 * PADDING - 22.0;
+    let font_size = (lines_height / pile_names.len().max(1) as f32).min(10.0);
+    let counts_x = x + width - 2.0 * PADDING - QR_SIZE - 24.0;
+    for (line, pile_name) in pile_names.iter().enumerate() {
+        let cards = pack
+            .card_sources
+            .iter()
+            .find(|(p, _)| p.as_ref() == *pile_name)
+            .map_or(0, |(_, cards)| *cards);
+        let line_y = top - 22.0 - (line + 1) as f32 * font_size;
+        content.begin_text();
+        content.set_font(FONT, font_size);
+        content.next_line(x + PADDING, line_y);
+        let name_width = counts_x - x - 2.0 * PADDING;
+        content.show(Str(&fit(&ascii(pile_name), name_width, font_size)));
+        content.end_text();
+        content.begin_text();
+        content.set_font(FONT, font_size);
+        content.next_line(counts_x, line_y);
+        content.show(Str(cards.to_string().as_bytes()));
+        content.end_text();
+    }
+
+    qr_code(
+        content,
+        &format!("CubeShuffle seed={} pack={}", seed, number),
+        x + width - PADDING - QR_SIZE,
+        y + PADDING,
+    );
+}
+
+// Packs are numbered from 1 and every label lists all piles in sorted order, so labels can be compared side by side.
+// The given piles are listed even when no pack has cards from them.
+pub fn labels<P>(packs: &[Pack<P>], piles: &[&str], seed: u64) -> Vec<u8>
+where
+    P: AsRef<str> + Hash + Eq + Serialize,
+{
+    let pile_names: Vec<&str> = packs
+        .iter()
+        .flat_map(|p| p.card_sources.keys().map(|k| k.as_ref()))
+        .chain(piles.iter().copied())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+
+    let per_page = COLUMNS * ROWS;
+    let page_count = packs.len().div_ceil(per_page).max(1);
+    let catalog_id = Ref::new(1);
+    let page_tree_id = Ref::new(2);
+    let font_id = Ref::new(3);
+    let page_ids: Vec<Ref> = (0..page_count)
+        .map(|i| Ref::new(4 + 2 * i as i32))
+        .collect();
+
+    let mut pdf = Pdf::new();
+    pdf.catalog(catalog_id).pages(page_tree_id);
+    pdf.pages(page_tree_id)
+        .kids(page_ids.iter().copied())
+        .count(page_count as i32);
+    pdf.type1_font(font_id).base_font(Name(b"Helvetica"));
+
+    let width = (PAGE_WIDTH - 2.0 * MARGIN) / COLUMNS as f32;
+    let height = (PAGE_HEIGHT - 2.0 * MARGIN) / ROWS as f32;
+    for (page_index, (page_id, page_packs)) in page_ids
+        .iter()
+        .zip(packs.chunks(per_page).chain(std::iter::once(&[][..])))
+        .enumerate()
+    {
+        let content_id = Ref::new(page_id.get() + 1);
+        let mut page = pdf.page(*page_id);
+        page.media_box(Rect::new(0.0, 0.0, PAGE_WIDTH, PAGE_HEIGHT));
+        page.parent(page_tree_id);
+        page.contents(content_id);
+        page.resources().fonts().pair(FONT, font_id);
+        page.finish();
+
+        let mut content = Content::new();
+        for (slot, pack) in page_packs.iter().enumerate() {
+            let x = MARGIN + (slot % COLUMNS) as f32 * width;
+            let y = PAGE_HEIGHT - MARGIN - (slot / COLUMNS + 1) as f32 * height;
+            let number = page_index * per_page + slot + 1;
+            label(&mut content, pack, &pile_names, number, seed, x, y);
+        }
+        pdf.stream(content_id, &content.finish());
+    }
+    pdf.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::fs;
+
+    use cube_shuffle_core::distribution_shuffle::Pack;
+
+    use crate::labels::labels;
+
+    const GOLDEN: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden/labels.pdf");
+
+    // Run with UPDATE_GOLDEN=1 to rewrite the golden file after an intended change of the layout.
+    #[test]
+    fn golden_labels() {
+        let packs: Vec<Pack<&str>> = (0..12)
+            .map(|i| Pack {
+                card_sources: HashMap::from([
+                    ("Blue", 1 + i % 3),
+                    ("Red", 3 - i % 3),
+                    ("Lands", 1),
+                ]),
+            })
+            .collect();
+        let pdf = labels(&packs, &["Blue", "Lands", "Red"], 42);
+        if std::env::var_os("UPDATE_GOLDEN").is_some() {
+            fs::write(GOLDEN, &pdf).unwrap();
+        }
+        assert_eq!(fs::read(GOLDEN).unwrap(), pdf);
+    }
+
+    #[test]
+    fn empty_labels() {
+        let packs: Vec<Pack<&str>> = Vec::new();
+        assert!(labels(&packs, &[], 0).starts_with(b"%PDF"));
+    }
+
+    #[test]
+    fn configured_piles() {
+        let packs: Vec<Pack<&str>> = vec![Pack {
+            card_sources: HashMap::from([("Red", 2)]),
+        }];
+        let long = "Multicolored and colorless artifacts of every kind";
+        let pdf = labels(&packs, &["Green", long, "Red"], 0);
+        let contains = |text: &[u8]| pdf.windows(text.len()).any(|w| w == text);
+        assert!(contains(b"(Green) Tj"));
+        assert!(contains(b"(Multicolored and colorless...) Tj"));
+        assert!(!contains(long.as_bytes()));
+    }
+}
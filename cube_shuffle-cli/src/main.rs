@@ -1,15 +1,34 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::fs::File;
+use std::io::{BufReader, IsTerminal};
+use std::path::PathBuf;
+use std::process;
 
 use clap::{Parser, Subcommand};
 use parse_display::{Display, FromStr};
 use rand::prelude::StdRng;
-use rand::SeedableRng;
+use rand::{RngCore, SeedableRng};
 
-use cube_shuffle_core::distribution_shuffle::{shuffle, Pile};
+use cube_shuffle_core::analysis::analyze_config;
+use cube_shuffle_core::commit_reveal;
+use cube_shuffle_core::commit_reveal::Transcript;
+use cube_shuffle_core::config::Config;
+use cube_shuffle_core::dice;
+use cube_shuffle_core::distribution_shuffle::{Odds, Pile, ShufflingErrors};
+use cube_shuffle_core::import;
+use cube_shuffle_core::import::ImportErrors;
+use cube_shuffle_core::report::html_report;
+use cube_shuffle_core::scryfall::CardDatabase;
+use cube_shuffle_core::seating::{seat, SeatingErrors};
+use cube_shuffle_core::seed::parse_seed;
 
 use crate::output::Formats;
 
+mod labels;
 mod output;
+mod sheet;
+mod verify;
 
 #[derive(Parser, Debug)]
 #[clap(author, version, about, long_about = None)]
@@ -34,6 +53,54 @@ struct Cli {
     one_indexed: bool,
 }
 
+#[derive(Subcommand, Debug)]
+enum SeedCommands {
+    Commit {
+        #[clap(short, long)]
+        #[clap(value_name = "secret")]
+        secret: String,
+
+        #[clap(short, long)]
+        #[clap(value_name = "player name")]
+        player: Option<String>,
+
+        #[clap(long, requires = "player")]
+        #[clap(value_name = "transcript file")]
+        transcript: Option<PathBuf>,
+    },
+    Reveal {
+        #[clap(short, long)]
+        #[clap(value_name = "secret")]
+        secret: String,
+
+        #[clap(short, long)]
+        #[clap(value_name = "nonce printed by commit")]
+        nonce: String,
+
+        #[clap(short, long)]
+        #[clap(value_name = "player name")]
+        player: String,
+
+        #[clap(long)]
+        #[clap(value_name = "transcript file")]
+        transcript: PathBuf,
+    },
+    Combine {
+        #[clap(long)]
+        #[clap(value_name = "transcript file")]
+        transcript: PathBuf,
+    },
+    Dice {
+        #[clap(short, long)]
+        #[clap(value_name = "sides of the die")]
+        #[clap(default_value_t = 6)]
+        sides: u32,
+
+        #[clap(value_name = "rolls")]
+        rolls: Vec<String>,
+    },
+}
+
 #[derive(Subcommand, Debug)]
 enum Commands {
     Distribution {
@@ -45,6 +112,67 @@ enum Commands {
         #[clap(value_name = "pack size")]
         #[clap(default_value_t = 15)]
         pack_size: usize,
+
+        #[clap(long)]
+        #[clap(value_name = "cube list file")]
+        import: Option<PathBuf>,
+
+        #[clap(short, long)]
+        #[clap(value_name = "column")]
+        group_by: Option<String>,
+
+        #[clap(long)]
+        #[clap(value_name = "randomness of imported piles")]
+        #[clap(default_value_t = 0.1)]
+        import_randomness: Odds,
+
+        #[clap(long)]
+        #[clap(value_name = "HTML report file")]
+        report: Option<PathBuf>,
+
+        #[clap(long)]
+        #[clap(value_name = "PDF label file")]
+        labels: Option<PathBuf>,
+
+        #[clap(long)]
+        #[clap(value_name = "config file")]
+        save_config: Option<PathBuf>,
+    },
+    Classify {
+        #[clap(long)]
+        #[clap(value_name = "cube list file")]
+        import: PathBuf,
+
+        #[clap(long)]
+        #[clap(value_name = "Scryfall bulk data file")]
+        scryfall: PathBuf,
+
+        #[clap(long)]
+        #[clap(value_name = "randomness of classified piles")]
+        #[clap(default_value_t = 0.1)]
+        randomness: Odds,
+    },
+    Seating {
+        #[clap(short, long)]
+        #[clap(value_name = "player name")]
+        players: Vec<String>,
+
+        #[clap(short, long)]
+        #[clap(value_name = "team count")]
+        teams: Option<usize>,
+    },
+    Seed {
+        #[clap(subcommand)]
+        command: SeedCommands,
+    },
+    Verify {
+        #[clap(long)]
+        #[clap(value_name = "config file")]
+        config: PathBuf,
+
+        #[clap(long)]
+        #[clap(value_name = "Json or Yaml packs file")]
+        result: PathBuf,
     },
 }
 
@@ -55,33 +183,241 @@ struct PileInput {
     definition: Pile,
 }
 
+// Problems with the cube list are the user's to fix, so they are printed instead of panicking.
+fn import_piles(path: &PathBuf, group_by: Option<&str>, randomness: Odds) -> HashMap<String, Pile> {
+    let text = fs::read_to_string(path).unwrap_or_else(|e| {
+        eprintln!("The cube list {} can not be read: {}", path.display(), e);
+        process::exit(1);
+    });
+    let groups = import::parse(&text).and_then(|cards| import::group(&cards, group_by));
+    match groups {
+        Ok(groups) => import::piles(&groups, randomness),
+        Err(e) => {
+            let message = match e {
+                ImportErrors::EmptyList => String::from("The cube list contains no cards."),
+                ImportErrors::MissingNameColumn => {
+                    String::from("The CSV file has no \"name\" column.")
+                }
+                ImportErrors::UnclosedQuote => String::from("The CSV file has an unclosed quote."),
+                ImportErrors::InvalidQuantity { line } => {
+                    format!("Invalid card quantity on line {}.", line)
+                }
+                ImportErrors::UnknownColumn { column } => {
+                    format!("The cube list has no \"{}\" column.", column)
+                }
+            };
+            eprintln!("{}", message);
+            process::exit(1);
+        }
+    }
+}
+
+fn read_transcript(path: &PathBuf) -> Transcript {
+    match path.exists() {
+        true => serde_yaml::from_str(&fs::read_to_string(path).unwrap()).unwrap(),
+        false => Transcript::default(),
+    }
+}
+
 fn main() {
     let cli: Cli = Cli::parse();
-    let mut rng = match cli.seed {
-        None => StdRng::from_entropy(),
-        Some(s) => StdRng::seed_from_u64(s),
-    };
+    let seed = cli
+        .seed
+        .unwrap_or_else(|| StdRng::from_entropy().next_u64());
     match &cli.command {
-        Commands::Distribution { piles, pack_size } => {
-            let piles_map: HashMap<_, _> = piles.iter().map(|p| (&p.name, p.definition)).collect();
-            let shuffled = shuffle(&piles_map, *pack_size, &mut rng).unwrap();
-            let output = match cli.indexed {
-                true => {
+        Commands::Distribution {
+            piles,
+            pack_size,
+            import,
+            group_by,
+            import_randomness,
+            report,
+            labels,
+            save_config,
+        } => {
+            let imported_piles = import.as_ref().map_or_else(HashMap::new, |path| {
+                import_piles(path, group_by.as_deref(), *import_randomness)
+            });
+            let config = Config::new(
+                seed.to_string(),
+                *pack_size,
+                imported_piles
+                    .into_iter()
+                    .chain(piles.iter().map(|p| (p.name.clone(), p.definition)))
+                    .collect(),
+            );
+            for warning in analyze_config(&config) {
+                eprintln!("Warning [{}]: {}", warning.code(), warning);
+            }
+            if let Some(save_config) = save_config {
+                let saved = match save_config.extension().and_then(|e| e.to_str()) {
+                    Some("json") => serde_json::to_string_pretty(&config).unwrap(),
+                    _ => serde_yaml::to_string(&config).unwrap(),
+                };
+                fs::write(save_config, saved).unwrap();
+            }
+            let shuffled = match config.shuffle() {
+                Ok(shuffled) => shuffled,
+                Err(ShufflingErrors::EmptyPacks) => {
+                    eprintln!("Packs need a size of at least 1.");
+                    process::exit(1);
+                }
+                Err(ShufflingErrors::CardOverflow {
+                    current_cards,
+                    max_cards,
+                }) => {
+                    eprintln!(
+                        "The piles have {} cards, but at most {} can be shuffled.",
+                        current_cards, max_cards
+                    );
+                    process::exit(1);
+                }
+            };
+            if let Some(report) = report {
+                fs::write(report, html_report(&config, &shuffled)).unwrap();
+            }
+            if let Some(labels) = labels {
+                let pile_names: Vec<&str> = config.piles.keys().map(String::as_str).collect();
+                fs::write(labels, labels::labels(&shuffled, &pile_names, seed)).unwrap();
+            }
+            let style = sheet::Style {
+                color: std::io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none(),
+                first_pack: match cli.indexed && !cli.one_indexed {
+                    true => 0,
+                    false => 1,
+                },
+            };
+            let pile_names: Vec<&str> = config.piles.keys().map(String::as_str).collect();
+            let output = match (&cli.format, cli.indexed) {
+                (Formats::Sheet, _) => sheet::pack_sheet(&shuffled, &config.piles, &style)
+                    .trim_end()
+                    .to_string(),
+                (Formats::PileSheet, _) => sheet::pile_sheet(&shuffled, &config.piles, &style)
+                    .trim_end()
+                    .to_string(),
+                (_, true) => {
                     let offset: usize = match cli.one_indexed {
                         true => 1,
                         false => 0,
                     };
-                    let output_data: HashMap<_, _> = shuffled
+                    let output_data: BTreeMap<_, _> = shuffled
                         .into_iter()
                         .enumerate()
                         .map(|(i, p)| (i + offset, p))
                         .collect();
-                    output::to_string(cli.format, output_data)
+                    output::to_string(cli.format, output_data, &pile_names)
                 }
-                false => output::to_string(cli.format, shuffled),
+                (_, false) => output::to_string(cli.format, shuffled, &pile_names),
             };
             println!("{}", output);
         }
+        Commands::Classify {
+            import,
+            scryfall,
+            randomness,
+        } => {
+            let cards = import::parse(&fs::read_to_string(import).unwrap()).unwrap();
+            let database =
+                CardDatabase::load(BufReader::new(File::open(scryfall).unwrap())).unwrap();
+            let classification = database.classify(&cards);
+            for unknown in classification.unknown.iter() {
+                eprintln!("Unknown card: {}", unknown);
+            }
+            let mut piles: Vec<PileInput> = import::piles(&classification.groups, *randomness)
+                .into_iter()
+                .map(|(name, definition)| PileInput { name, definition })
+                .collect();
+            piles.sort_by(|a, b| a.name.cmp(&b.name));
+            for pile in piles {
+                println!("{}", pile);
+            }
+        }
+        Commands::Seating { players, teams } => {
+            let seating = match seat(players, *teams, seed) {
+                Ok(seating) => seating,
+                Err(SeatingErrors::EmptyTeams) => {
+                    eprintln!("There has to be at least one team.");
+                    process::exit(1);
+                }
+                Err(SeatingErrors::UnevenTeams { players, teams }) => {
+                    eprintln!(
+                        "{} players can not be split evenly into {} teams.",
+                        players, teams
+                    );
+                    process::exit(1);
+                }
+            };
+            println!("{}", output::to_string(cli.format, seating, &[]));
+        }
+        Commands::Seed { command } => match command {
+            SeedCommands::Commit {
+                secret,
+                player,
+                transcript,
+            } => {
+                let nonce = commit_reveal::nonce(&mut StdRng::from_entropy());
+                let hash = commit_reveal::commitment(&nonce, secret);
+                if let Some(path) = transcript {
+                    let mut saved = read_transcript(path);
+                    // Clap requires a player along with the transcript.
+                    saved.commit(player.clone().unwrap(), hash.clone()).unwrap();
+                    fs::write(path, serde_yaml::to_string(&saved).unwrap()).unwrap();
+                }
+                println!("{}", hash);
+                println!("Keep the nonce {} secret until revealing.", nonce);
+            }
+            SeedCommands::Reveal {
+                secret,
+                nonce,
+                player,
+                transcript,
+            } => {
+                let mut saved = read_transcript(transcript);
+                saved.reveal(player, nonce.clone(), secret.clone()).unwrap();
+                fs::write(transcript, serde_yaml::to_string(&saved).unwrap()).unwrap();
+            }
+            SeedCommands::Combine { transcript } => {
+                let combined = read_transcript(transcript).combine().unwrap();
+                println!("{}", parse_seed(&combined));
+            }
+            SeedCommands::Dice { sides, rolls } => {
+                let rolls = dice::parse_rolls(&rolls.join(" ")).unwrap();
+                let dice_seed = dice::dice_seed(*sides, &rolls).unwrap();
+                match dice_seed.seed {
+                    Some(seed) => println!("{}", seed),
+                    None => {
+                        eprintln!(
+                            "The rolls give {} of {} bits, roll about {} more times.",
+                            dice_seed.bits,
+                            dice::SEED_BITS,
+                            dice_seed.rolls_needed
+                        );
+                        process::exit(1);
+                    }
+                }
+            }
+        },
+        Commands::Verify { config, result } => {
+            let config: Config =
+                serde_yaml::from_str(&fs::read_to_string(config).unwrap()).unwrap();
+            config.validate().unwrap();
+            let packs =
+                serde_yaml::from_str::<verify::SavedPacks>(&fs::read_to_string(result).unwrap())
+                    .unwrap()
+                    .into_packs();
+            if let Some(warning) = verify::version_warning(&config) {
+                eprintln!("{}", warning);
+            }
+            let problems = verify::verify(&config, &packs);
+            if problems.is_empty() {
+                println!("The {} packs match the config.", packs.len());
+            } else {
+                for problem in problems {
+                    println!("{}", problem);
+                }
+                process::exit(1);
+            }
+        }
     }
 }
 
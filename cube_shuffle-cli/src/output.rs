@@ -1,19 +1,193 @@
+use std::collections::{BTreeMap, BTreeSet};
 use std::fmt::Debug;
+use std::hash::Hash;
 
 use parse_display::FromStr;
 use serde::Serialize;
 
+use cube_shuffle_core::distribution_shuffle::Pack;
+use cube_shuffle_core::seating::Seating;
+
 #[derive(Debug, FromStr)]
 pub enum Formats {
     Debug,
     PrettyDebug,
     Json,
     Yaml,
+    Csv,
+    Markdown,
+    Sheet,
+    PileSheet,
+}
+
+pub struct Table {
+    header: Vec<String>,
+    rows: Vec<Vec<String>>,
+}
+
+// Output which can be written as a table, with one row per pack or seat.
+// Packs get a column for each of the piles, even when no pack has cards from it.
+pub trait Tabular {
+    fn table(&self, piles: &[&str]) -> Table;
+}
+
+fn pack_table<'a, P>(
+    packs: impl Iterator<Item = (Option<usize>, &'a Pack<P>)> + Clone,
+    piles: &[&'a str],
+) -> Table
+where
+    P: AsRef<str> + Hash + Eq + Serialize + 'a,
+{
+    let pile_names: BTreeSet<&str> = packs
+        .clone()
+        .flat_map(|(_, p)| p.card_sources.keys().map(|k| k.as_ref()))
+        .chain(piles.iter().copied())
+        .collect();
+    let indexed = packs.clone().any(|(i, _)| i.is_some());
+
+    let mut header: Vec<String> = Vec::new();
+    if indexed {
+        header.push(String::from("Pack"));
+    }
+    header.extend(pile_names.iter().map(|p| p.to_string()));
+
+    let rows = packs
+        .map(|(index, pack)| {
+            index
+                .map(|i| i.to_string())
+                .into_iter()
+                .chain(pile_names.iter().map(|pile_name| {
+                    pack.card_sources
+                        .iter()
+                        .find(|(p, _)| p.as_ref() == *pile_name)
+                        .map_or(0, |(_, cards)| *cards)
+                        .to_string()
+                }))
+                .collect()
+        })
+        .collect();
+    Table { header, rows }
+}
+
+impl<P> Tabular for Vec<Pack<P>>
+where
+    P: AsRef<str> + Hash + Eq + Serialize,
+{
+    fn table(&self, piles: &[&str]) -> Table {
+        pack_table(self.iter().map(|p| (None, p)), piles)
+    }
 }
 
-pub fn to_string<O>(format: Formats, output_data: O) -> String
+impl<P> Tabular for BTreeMap<usize, Pack<P>>
 where
-    O: Debug + Serialize,
+    P: AsRef<str> + Hash + Eq + Serialize,
+{
+    fn table(&self, piles: &[&str]) -> Table {
+        pack_table(self.iter().map(|(i, p)| (Some(*i), p)), piles)
+    }
+}
+
+impl<P> Tabular for Seating<P>
+where
+    P: AsRef<str> + Serialize,
+{
+    fn table(&self, _: &[&str]) -> Table {
+        let mut header = vec![String::from("Seat"), String::from("Player")];
+        if !self.teams.is_empty() {
+            header.push(String::from("Team"));
+        }
+        let rows = self
+            .seats
+            .iter()
+            .enumerate()
+            .map(|(seat, player)| {
+                let mut row = vec![(seat + 1).to_string(), player.as_ref().to_string()];
+                if !self.teams.is_empty() {
+                    let team = self
+                        .teams
+                        .iter()
+                        .position(|team| team.iter().any(|p| p.as_ref() == player.as_ref()));
+                    row.push(team.map_or_else(String::new, |t| (t + 1).to_string()));
+                }
+                row
+            })
+            .collect();
+        Table { header, rows }
+    }
+}
+
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+fn csv(table: Table) -> String {
+    std::iter::once(table.header)
+        .chain(table.rows)
+        .map(|row| {
+            row.iter()
+                .map(|f| csv_field(f))
+                .collect::<Vec<_>>()
+                .join(",")
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn markdown_row(row: &[String]) -> String {
+    let cells: Vec<String> = row
+        .iter()
+        .map(|f| f.replace('|', "\\|").replace('\n', " "))
+        .collect();
+    format!("| {} |", cells.join(" | "))
+}
+
+fn markdown(table: Table) -> String {
+    let separator: Vec<String> = table.header.iter().map(|_| String::from("---")).collect();
+    std::iter::once(markdown_row(&table.header))
+        .chain(std::iter::once(markdown_row(&separator)))
+        .chain(table.rows.iter().map(|r| markdown_row(r)))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+// Aligned columns with a checkbox per row, used by the sheet formats for output other than packs.
+fn checklist(table: Table) -> String {
+    let widths: Vec<usize> = table
+        .header
+        .iter()
+        .enumerate()
+        .map(|(column, header)| {
+            table
+                .rows
+                .iter()
+                .map(|r| r[column].chars().count())
+                .chain(std::iter::once(header.chars().count()))
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+    let line = |row: &[String]| {
+        row.iter()
+            .zip(widths.iter())
+            .map(|(field, width)| format!("{:<width$}", field))
+            .collect::<Vec<_>>()
+            .join("  ")
+            .trim_end()
+            .to_string()
+    };
+    std::iter::once(format!("    {}", line(&table.header)))
+        .chain(table.rows.iter().map(|r| format!("[ ] {}", line(r))))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+pub fn to_string<O>(format: Formats, output_data: O, piles: &[&str]) -> String
+where
+    O: Debug + Serialize + Tabular,
 {
     match format {
         Formats::Debug => {
@@ -24,5 +198,66 @@ where
         }
         Formats::Json => serde_json::to_string(&output_data).unwrap(),
         Formats::Yaml => serde_yaml::to_string(&output_data).unwrap(),
+        Formats::Csv => csv(output_data.table(piles)),
+        Formats::Markdown => markdown(output_data.table(piles)),
+        Formats::Sheet | Formats::PileSheet => checklist(output_data.table(piles)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{BTreeMap, HashMap};
+
+    use cube_shuffle_core::distribution_shuffle::Pack;
+    use cube_shuffle_core::seating::Seating;
+
+    use crate::output::{to_string, Formats};
+
+    fn packs() -> Vec<Pack<&'static str>> {
+        vec![
+            Pack {
+                card_sources: HashMap::from([("Red", 2), ("Blue, Black", 1)]),
+            },
+            Pack {
+                card_sources: HashMap::from([("Red", 1), ("Green", 2)]),
+            },
+        ]
+    }
+
+    #[test]
+    fn csv() {
+        assert_eq!(
+            "\"Blue, Black\",Green,Red\n1,0,2\n0,2,1",
+            to_string(Formats::Csv, packs(), &[])
+        );
+        assert_eq!(
+            "\"Blue, Black\",Green,Lands,Red\n1,0,0,2\n0,2,0,1",
+            to_string(Formats::Csv, packs(), &["Lands", "Red"])
+        );
+    }
+
+    #[test]
+    fn indexed_markdown() {
+        let indexed: BTreeMap<usize, _> = packs()
+            .into_iter()
+            .enumerate()
+            .map(|(i, p)| (i + 1, p))
+            .collect();
+        assert_eq!(
+            "| Pack | Blue, Black | Green | Red |\n| --- | --- | --- | --- |\n| 1 | 1 | 0 | 2 |\n| 2 | 0 | 2 | 1 |",
+            to_string(Formats::Markdown, indexed, &[])
+        );
+    }
+
+    #[test]
+    fn seating_teams() {
+        let seating = Seating {
+            seats: vec!["Ann", "Bob", "Cat", "Dan"],
+            teams: vec![vec!["Ann", "Bob"], vec!["Cat", "Dan"]],
+        };
+        assert_eq!(
+            "Seat,Player,Team\n1,Ann,1\n2,Bob,1\n3,Cat,2\n4,Dan,2",
+            to_string(Formats::Csv, seating, &[])
+        );
     }
 }
@@ -0,0 +1,235 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use serde::Serialize;
+
+use cube_shuffle_core::distribution_shuffle::{Pack, Pile};
+
+const BOLD: &str = "\x1b[1m";
+const CYAN: &str = "\x1b[36m";
+const YELLOW: &str = "\x1b[33m";
+const RESET: &str = "\x1b[0m";
+
+pub struct Style {
+    pub color: bool,
+    pub first_pack: usize,
+}
+
+impl Style {
+    fn paint(&self, color: &str, text: &str) -> String {
+        match self.color {
+            true => format!("{}{}{}", color, text, RESET),
+            false => text.to_string(),
+        }
+    }
+}
+
+fn pad(text: &str, width: usize) -> String {
+    format!("{}{}", text, " ".repeat(width - text.chars().count()))
+}
+
+fn cards_in<P: AsRef<str> + Hash + Eq + Serialize>(pack: &Pack<P>, pile_name: &str) -> usize {
+    pack.card_sources
+        .iter()
+        .find(|(p, _)| p.as_ref() == pile_name)
+        .map_or(0, |(_, cards)| *cards)
+}
+
+fn leftovers<P>(packs: &[Pack<P>], piles: &HashMap<String, Pile>, style: &Style) -> String
+where
+    P: AsRef<str> + Hash + Eq + Serialize,
+{
+    let mut pile_names: Vec<&String> = piles.keys().collect();
+    pile_names.sort();
+    let left: Vec<(&String, usize)> = pile_names
+        .into_iter()
+        .map(|pile_name| {
+            let used: usize = packs.iter().map(|p| cards_in(p, pile_name)).sum();
+            (pile_name, piles[pile_name].cards.saturating_sub(used))
+        })
+        .filter(|(_, cards)| *cards > 0)
+        .collect();
+
+    let mut sheet = style.paint(BOLD, "Leftovers");
+    sheet.push('\n');
+    if left.is_empty() {
+        sheet.push_str("  No cards are left over.\n");
+    }
+    let width = left
+        .iter()
+        .map(|(p, _)| p.chars().count())
+        .max()
+        .unwrap_or(0);
+    let count_width = left
+        .iter()
+        .map(|(_, c)| c.to_string().len())
+        .max()
+        .unwrap_or(0);
+    for (pile_name, cards) in left {
+        sheet.push_str(&format!(
+            "  {}  {:>count_width$}\n",
+            style.paint(CYAN, &pad(pile_name, width)),
+            cards
+        ));
+    }
+    sheet
+}
+
+// One block per pack with a checkbox per pile, for building the packs one at a time.
+pub fn pack_sheet<P>(packs: &[Pack<P>], piles: &HashMap<String, Pile>, style: &Style) -> String
+where
+    P: AsRef<str> + Hash + Eq + Serialize,
+{
+    let mut pile_names: Vec<&str> = packs
+        .iter()
+        .flat_map(|p| p.card_sources.keys().map(|k| k.as_ref()))
+        .collect();
+    pile_names.sort();
+    pile_names.dedup();
+    let width = pile_names
+        .iter()
+        .map(|p| p.chars().count())
+        .max()
+        .unwrap_or(0);
+    let count_width = packs
+        .iter()
+        .flat_map(|p| p.card_sources.values())
+        .map(|c| c.to_string().len())
+        .max()
+        .unwrap_or(0);
+
+    let mut sheet = String::new();
+    for (index, pack) in packs.iter().enumerate() {
+        let title = format!("Pack {}", index + style.first_pack);
+        sheet.push_str(&style.paint(BOLD, &title));
+        sheet.push('\n');
+        for pile_name in pile_names.iter() {
+            let cards = cards_in(pack, pile_name);
+            if cards == 0 {
+                continue;
+            }
+            sheet.push_str(&format!(
+                "  [ ] {}  {:>count_width$}\n",
+                style.paint(CYAN, &pad(pile_name, width)),
+                cards
+            ));
+        }
+        sheet.push('\n');
+    }
+    sheet.push_str(&leftovers(packs, piles, style));
+    sheet
+}
+
+// One block per pile with a checkbox per pack, for dealing a whole pile before picking up the next.
+pub fn pile_sheet<P>(packs: &[Pack<P>], piles: &HashMap<String, Pile>, style: &Style) -> String
+where
+    P: AsRef<str> + Hash + Eq + Serialize,
+{
+    let mut pile_names: Vec<&String> = piles.keys().collect();
+    pile_names.sort();
+    let last_pack = format!(
+        "Pack {}",
+        (packs.len() + style.first_pack).saturating_sub(1)
+    );
+    let width = last_pack.len();
+    let count_width = packs
+        .iter()
+        .flat_map(|p| p.card_sources.values())
+        .map(|c| c.to_string().len())
+        .max()
+        .unwrap_or(0);
+
+    let mut sheet = String::new();
+    for pile_name in pile_names {
+        let dealt: Vec<(usize, usize)> = packs
+            .iter()
+            .enumerate()
+            .map(|(index, pack)| (index + style.first_pack, cards_in(pack, pile_name)))
+            .filter(|(_, cards)| *cards > 0)
+            .collect();
+        if dealt.is_empty() {
+            continue;
+        }
+        let title = format!(
+            "{} ({} cards)",
+            pile_name,
+            dealt.iter().map(|(_, c)| c).sum::<usize>()
+        );
+        sheet.push_str(&style.paint(BOLD, &title));
+        sheet.push('\n');
+        for (number, cards) in dealt {
+            sheet.push_str(&format!(
+                "  [ ] {}  {}\n",
+                style.paint(YELLOW, &pad(&format!("Pack {}", number), width)),
+                style.paint(CYAN, &format!("{:>count_width$}", cards))
+            ));
+        }
+        sheet.push('\n');
+    }
+    sheet.push_str(&leftovers(packs, piles, style));
+    sheet
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use cube_shuffle_core::distribution_shuffle::{Pack, Pile};
+
+    use crate::sheet::{pack_sheet, pile_sheet, Style};
+
+    fn deal() -> (Vec<Pack<&'static str>>, HashMap<String, Pile>) {
+        let packs = vec![
+            Pack {
+                card_sources: HashMap::from([("Red", 2), ("Blue", 10)]),
+            },
+            Pack {
+                card_sources: HashMap::from([("Red", 3), ("Blue", 9)]),
+            },
+        ];
+        let piles = HashMap::from([
+            (
+                String::from("Red"),
+                Pile {
+                    cards: 6,
+                    randomness: 0.0,
+                },
+            ),
+            (
+                String::from("Blue"),
+                Pile {
+                    cards: 19,
+                    randomness: 0.0,
+                },
+            ),
+        ]);
+        (packs, piles)
+    }
+
+    const PLAIN: Style = Style {
+        color: false,
+        first_pack: 1,
+    };
+
+    #[test]
+    fn packs_first() {
+        let (packs, piles) = deal();
+        assert_eq!(
+            "Pack 1\n  [ ] Blue  10\n  [ ] Red    2\n\n\
+            Pack 2\n  [ ] Blue   9\n  [ ] Red    3\n\n\
+            Leftovers\n  Red  1\n",
+            pack_sheet(&packs, &piles, &PLAIN)
+        );
+    }
+
+    #[test]
+    fn piles_first() {
+        let (packs, piles) = deal();
+        assert_eq!(
+            "Blue (19 cards)\n  [ ] Pack 1  10\n  [ ] Pack 2   9\n\n\
+            Red (5 cards)\n  [ ] Pack 1   2\n  [ ] Pack 2   3\n\n\
+            Leftovers\n  Red  1\n",
+            pile_sheet(&packs, &piles, &PLAIN)
+        );
+    }
+}
@@ -0,0 +1,221 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use serde::Deserialize;
+
+use cube_shuffle_core::config::{Config, VERSION};
+use cube_shuffle_core::distribution_shuffle::Pack;
+use cube_shuffle_core::verify::{verify_packs, Violation};
+
+// Results are accepted as written by the Json and Yaml formats, both with and without --indexed.
+#[derive(Deserialize)]
+#[serde(untagged)]
+pub enum SavedPacks {
+    Listed(Vec<Pack<String>>),
+    Indexed(HashMap<PackIndex, Pack<String>>),
+}
+
+// Json object keys are always strings, while Yaml keeps the indices as numbers.
+#[derive(Deserialize, PartialEq, Eq, Hash)]
+#[serde(untagged)]
+pub enum PackIndex {
+    Number(usize),
+    Text(String),
+}
+
+impl PackIndex {
+    fn number(&self) -> usize {
+        match self {
+            PackIndex::Number(n) => *n,
+            PackIndex::Text(t) => t.parse().unwrap_or(usize::MAX),
+        }
+    }
+}
+
+impl SavedPacks {
+    pub fn into_packs(self) -> Vec<Pack<String>> {
+        match self {
+            SavedPacks::Listed(packs) => packs,
+            SavedPacks::Indexed(packs) => {
+                let mut indexed: Vec<(PackIndex, Pack<String>)> = packs.into_iter().collect();
+                indexed.sort_by_key(|(i, _)| i.number());
+                indexed.into_iter().map(|(_, p)| p).collect()
+            }
+        }
+    }
+}
+
+fn counts<P: AsRef<str>>(
+    card_sources: impl Iterator<Item = (P, usize)>,
+) -> BTreeMap<String, usize> {
+    card_sources
+        .filter(|(_, cards)| *cards > 0)
+        .map(|(pile, cards)| (pile.as_ref().to_string(), cards))
+        .collect()
+}
+
+fn first_difference(config: &Config, packs: &[Pack<String>]) -> Option<String> {
+    let expected = match config.shuffle() {
+        Ok(expected) => expected,
+        Err(e) => return Some(format!("The config can not be shuffled: {:?}", e)),
+    };
+    if expected.len() != packs.len() {
+        return Some(format!(
+            "Expected {} packs but found {}.",
+            expected.len(),
+            packs.len()
+        ));
+    }
+    for (index, (expected_pack, pack)) in expected.iter().zip(packs).enumerate() {
+        let expected_counts = counts(expected_pack.card_sources.iter().map(|(p, c)| (*p, *c)));
+        let found_counts = counts(pack.card_sources.iter().map(|(p, c)| (p, *c)));
+        let pile_names: BTreeSet<&String> =
+            expected_counts.keys().chain(found_counts.keys()).collect();
+        for pile_name in pile_names {
+            let expected_cards = expected_counts.get(pile_name).unwrap_or(&0);
+            let found_cards = found_counts.get(pile_name).unwrap_or(&0);
+            if expected_cards != found_cards {
+                return Some(format!(
+                    "Pack {} should have {} cards from {} but has {}.",
+                    index + 1,
+                    expected_cards,
+                    pile_name,
+                    found_cards
+                ));
+            }
+        }
+    }
+    None
+}
+
+fn invariants(config: &Config, packs: &[Pack<String>]) -> Vec<String> {
+    verify_packs(&config.piles, config.pack_size, packs)
+        .into_iter()
+        .map(|violation| match violation {
+            Violation::WrongPackSize {
+                pack,
+                cards,
+                pack_size,
+            } => format!(
+                "Pack {} has {} cards instead of {}.",
+                pack + 1,
+                cards,
+                pack_size
+            ),
+            Violation::OverAllocatedPile { pile, cards, dealt } => format!(
+                "Pile {} has {} cards but {} were dealt.",
+                pile, cards, dealt
+            ),
+            Violation::UnderAllocatedPile { pile, cards, dealt } => format!(
+                "Pile {} has {} cards but only {} were dealt.",
+                pile, cards, dealt
+            ),
+            Violation::UnknownPile { pack, pile } => format!(
+                "Pack {} has cards from the unknown pile {}.",
+                pack + 1,
+                pile
+            ),
+            Violation::CardOverflow { cards, max_cards } => format!(
+                "The piles have {} cards, but at most {} can be shuffled.",
+                cards, max_cards
+            ),
+            Violation::PackCountMismatch { expected, found } => format!(
+                "The piles make {} packs but {} were dealt.",
+                expected, found
+            ),
+        })
+        .collect()
+}
+
+// The same seed may give other packs in other versions, so a mismatch explains a failed verification.
+pub fn version_warning(config: &Config) -> Option<String> {
+    if config.version == VERSION {
+        return None;
+    }
+    Some(format!(
+        "The config was shuffled with version {} but this is version {}.",
+        config.version, VERSION
+    ))
+}
+
+// Returns every problem found, where the result only passes when there are none.
+pub fn verify(config: &Config, packs: &[Pack<String>]) -> Vec<String> {
+    first_difference(config, packs)
+        .into_iter()
+        .chain(invariants(config, packs))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use cube_shuffle_core::config::Config;
+    use cube_shuffle_core::distribution_shuffle::{Pack, Pile};
+
+    use crate::verify::{verify, version_warning};
+
+    fn config() -> Config {
+        Config::new(
+            String::from("Friday"),
+            3,
+            HashMap::from([
+                (
+                    String::from("Red"),
+                    Pile {
+                        cards: 5,
+                        randomness: 0.5,
+                    },
+                ),
+                (
+                    String::from("Blue"),
+                    Pile {
+                        cards: 5,
+                        randomness: 0.0,
+                    },
+                ),
+            ]),
+        )
+    }
+
+    fn owned(config: &Config) -> Vec<Pack<String>> {
+        config
+            .shuffle()
+            .unwrap()
+            .into_iter()
+            .map(|p| Pack {
+                card_sources: p
+                    .card_sources
+                    .into_iter()
+                    .map(|(k, v)| (k.clone(), v))
+                    .collect(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn matching_packs() {
+        let config = config();
+        assert!(verify(&config, &owned(&config)).is_empty());
+    }
+
+    #[test]
+    fn edited_packs() {
+        let config = config();
+        let mut packs = owned(&config);
+        *packs[1].card_sources.get_mut("Red").unwrap() += 1;
+        let problems = verify(&config, &packs);
+        assert!(problems[0].starts_with("Pack 2 should have"));
+        assert_eq!("Pack 2 has 4 cards instead of 3.", problems[1]);
+    }
+
+    #[test]
+    fn missing_version() {
+        let config = config();
+        assert_eq!(None, version_warning(&config));
+        let json = r#"{"seed":"Friday","pack_size":3,"piles":{}}"#;
+        let old: Config = serde_json::from_str(json).unwrap();
+        assert!(version_warning(&old)
+            .unwrap()
+            .starts_with("The config was shuffled with version unknown"));
+    }
+}
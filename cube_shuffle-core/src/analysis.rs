@@ -0,0 +1,177 @@
+use parse_display::Display;
+use serde::{Deserialize, Serialize};
+
+use crate::analysis::ConfigWarning::{IneffectiveRandomness, LargeLeftovers, NoPacks, SparsePile};
+use crate::config::Config;
+use crate::distribution_shuffle::Odds;
+
+// Configs which shuffle fine but are probably not what was meant.
+#[derive(Clone, Debug, PartialEq, Eq, Display, Serialize, Deserialize)]
+pub enum ConfigWarning {
+    #[display("{cards} cards are not enough for a single pack of {pack_size}.")]
+    NoPacks { cards: usize, pack_size: usize },
+    #[display(
+        "{pile} is the only pile with randomness, which has no effect without a second one."
+    )]
+    IneffectiveRandomness { pile: String },
+    #[display(
+        "{pile} at 0% randomness with {cards} cards < {packs} packs: {empty_packs} packs get none."
+    )]
+    SparsePile {
+        pile: String,
+        cards: usize,
+        packs: usize,
+        empty_packs: usize,
+    },
+    #[display("{leftovers} cards are left over, {missing} more would fill another pack.")]
+    LargeLeftovers { leftovers: usize, missing: usize },
+}
+
+impl ConfigWarning {
+    // Stable codes, so warnings can be looked up or filtered regardless of the wording.
+    pub fn code(&self) -> &'static str {
+        match self {
+            NoPacks { .. } => "no-packs",
+            IneffectiveRandomness { .. } => "ineffective-randomness",
+            SparsePile { .. } => "sparse-pile",
+            LargeLeftovers { .. } => "large-leftovers",
+        }
+    }
+}
+
+// Invalid configs are left to Config::validate, they give no warnings.
+pub fn analyze_config(config: &Config) -> Vec<ConfigWarning> {
+    let mut piles: Vec<(&String, usize, Odds)> = config
+        .piles
+        .iter()
+        .map(|(name, pile)| (name, pile.cards, pile.randomness))
+        .collect();
+    piles.sort_by(|a, b| a.0.cmp(b.0));
+    // Too many cards to count are left to shuffle, which reports them as a card overflow.
+    let cards = match piles
+        .iter()
+        .try_fold(0usize, |sum, (_, cards, _)| sum.checked_add(*cards))
+    {
+        Some(cards) => cards,
+        None => return Vec::new(),
+    };
+    if config.pack_size == 0 || cards == 0 {
+        return Vec::new();
+    }
+    let packs = cards / config.pack_size;
+    if packs == 0 {
+        return vec![NoPacks {
+            cards,
+            pack_size: config.pack_size,
+        }];
+    }
+
+    let mut warnings = Vec::new();
+    let random_piles: Vec<&String> = piles
+        .iter()
+        .filter(|(_, cards, randomness)| *cards > 0 && *randomness > 0.0)
+        .map(|(name, _, _)| *name)
+        .collect();
+    if let [pile] = random_piles[..] {
+        warnings.push(IneffectiveRandomness { pile: pile.clone() });
+    }
+    for (name, cards, randomness) in piles {
+        if randomness == 0.0 && cards < packs {
+            warnings.push(SparsePile {
+                pile: name.clone(),
+                cards,
+                packs,
+                empty_packs: packs - cards,
+            });
+        }
+    }
+    let leftovers = cards % config.pack_size;
+    if leftovers * 2 >= config.pack_size && leftovers > 0 {
+        warnings.push(LargeLeftovers {
+            leftovers,
+            missing: config.pack_size - leftovers,
+        });
+    }
+    warnings
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use crate::analysis::analyze_config;
+    use crate::analysis::ConfigWarning::{
+        IneffectiveRandomness, LargeLeftovers, NoPacks, SparsePile,
+    };
+    use crate::config::Config;
+    use crate::distribution_shuffle::Pile;
+
+    fn config(pack_size: usize, piles: &[(&str, usize, f64)]) -> Config {
+        Config::new(
+            String::from("1"),
+            pack_size,
+            piles
+                .iter()
+                .map(|(name, cards, randomness)| {
+                    (
+                        String::from(*name),
+                        Pile {
+                            cards: *cards,
+                            randomness: *randomness,
+                        },
+                    )
+                })
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn warnings() {
+        assert_eq!(
+            vec![NoPacks {
+                cards: 10,
+                pack_size: 15
+            }],
+            analyze_config(&config(15, &[("Green", 10, 0.1)]))
+        );
+        let warnings = analyze_config(&config(15, &[("Green", 348, 0.1), ("Lands", 20, 0.0)]));
+        assert_eq!(
+            vec![
+                IneffectiveRandomness {
+                    pile: String::from("Green")
+                },
+                SparsePile {
+                    pile: String::from("Lands"),
+                    cards: 20,
+                    packs: 24,
+                    empty_packs: 4
+                },
+                LargeLeftovers {
+                    leftovers: 8,
+                    missing: 7
+                },
+            ],
+            warnings
+        );
+        assert_eq!(
+            "Lands at 0% randomness with 20 cards < 24 packs: 4 packs get none.",
+            warnings[1].to_string()
+        );
+        assert_eq!("sparse-pile", warnings[1].code());
+        assert!(analyze_config(&config(15, &[("A", usize::MAX, 0.0), ("B", 1, 0.0)])).is_empty());
+    }
+
+    proptest! {
+        #[test]
+        fn even_colors_are_fine(colors in 2..10usize, packs in 1..50usize, pack_size in 1..20usize) {
+            let per_color = packs * pack_size;
+            let names: Vec<String> = (0..colors).map(|c| c.to_string()).collect();
+            let piles: Vec<(&str, usize, f64)> = names
+                .iter()
+                .map(|name| (name.as_str(), per_color, 0.1))
+                .collect();
+            let config = config(pack_size, &piles);
+            prop_assert!(analyze_config(&config).is_empty());
+        }
+    }
+}
@@ -0,0 +1,420 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use parse_display::{Display, FromStr};
+use rand::prelude::SliceRandom;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+
+use crate::balance::BalancingErrors::NotEnoughCards;
+use crate::distribution_shuffle::Pack;
+use crate::seed::{seeded_rng, Stream};
+
+pub type Rating = f64;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RatedCard<C> {
+    pub name: C,
+    pub rating: Rating,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Display, FromStr, Serialize, Deserialize)]
+pub enum BalanceMetric {
+    Total,
+    Top,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BalanceOptions {
+    pub metric: BalanceMetric,
+    pub tolerance: Rating,
+    pub max_swaps: usize,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RatedPack<P, C>
+where
+    P: Hash + Eq,
+{
+    pub cards: HashMap<P, Vec<C>>,
+    pub rating: Rating,
+    pub deviation: Rating,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Balanced<P, C>
+where
+    P: Hash + Eq,
+{
+    pub packs: Vec<RatedPack<P, C>>,
+    pub leftovers: HashMap<P, Vec<C>>,
+    pub average: Rating,
+    pub spread: Rating,
+    pub swaps: usize,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub enum BalancingErrors<P> {
+    NotEnoughCards {
+        pile: P,
+        needed: usize,
+        available: usize,
+    },
+}
+
+// Sums in pile order, as floats added in hash map order may round differently between runs.
+fn rate<P: Ord, C>(pack: &HashMap<P, Vec<&RatedCard<C>>>, metric: BalanceMetric) -> Rating {
+    let mut piles: Vec<(&P, &Vec<&RatedCard<C>>)> = pack.iter().collect();
+    piles.sort_unstable_by(|a, b| a.0.cmp(b.0));
+    let ratings = piles
+        .into_iter()
+        .flat_map(|(_, cards)| cards)
+        .map(|c| c.rating);
+    match metric {
+        BalanceMetric::Total => ratings.sum(),
+        BalanceMetric::Top => ratings.fold(0.0, Rating::max),
+    }
+}
+
+fn average(ratings: &[Rating]) -> Rating {
+    if ratings.is_empty() {
+        0.0
+    } else {
+        ratings.iter().sum::<Rating>() / ratings.len() as Rating
+    }
+}
+
+fn extremes(ratings: &[Rating]) -> (usize, usize) {
+    let mut lowest: usize = 0;
+    let mut highest: usize = 0;
+    for (index, rating) in ratings.iter().enumerate() {
+        if *rating < ratings[lowest] {
+            lowest = index;
+        }
+        if *rating > ratings[highest] {
+            highest = index;
+        }
+    }
+    (lowest, highest)
+}
+
+// Deals the rated cards of each pile into the packs and then repeatedly swaps cards of the same pile between the highest
+// and lowest rated pack until every pack is within the tolerance of the average or no swap improves them.
+// Swapping within piles keeps the pile counts of every pack, and with it the distribution, intact.
+pub fn balance<'a, P, C>(
+    packs: &[Pack<&P>],
+    cards: &'a HashMap<P, Vec<RatedCard<C>>>,
+    options: &BalanceOptions,
+    random: &mut impl RngCore,
+) -> Result<Balanced<&'a P, &'a RatedCard<C>>, BalancingErrors<P>>
+where
+    P: Clone + Eq + Hash + Ord + Serialize + Stream,
+{
+    for pack in packs {
+        for (pile_name, needed) in pack.card_sources.iter() {
+            if !cards.contains_key(*pile_name) && *needed > 0 {
+                return Err(NotEnoughCards {
+                    pile: (*pile_name).clone(),
+                    needed: *needed,
+                    available: 0,
+                });
+            }
+        }
+    }
+
+    let seed = random.next_u64();
+    let mut ordered_piles: Vec<(&P, &Vec<RatedCard<C>>)> = cards.iter().collect();
+    ordered_piles.sort_unstable_by_key(|(pile_name, _)| *pile_name);
+
+    let mut dealt: Vec<HashMap<&P, Vec<&RatedCard<C>>>> = vec![HashMap::new(); packs.len()];
+    let mut leftovers: HashMap<&P, Vec<&RatedCard<C>>> = HashMap::new();
+    for (pile_name, pile_cards) in ordered_piles {
+        let mut deck: Vec<&RatedCard<C>> = pile_cards.iter().collect();
+        deck.shuffle(&mut seeded_rng(seed, &("deal", pile_name)));
+        for (pack, dealt_pack) in packs.iter().zip(dealt.iter_mut()) {
+            let needed = pack.card_sources.get(pile_name).copied().unwrap_or(0);
+            if needed > deck.len() {
+                return Err(NotEnoughCards {
+                    pile: pile_name.clone(),
+                    needed,
+                    available: deck.len(),
+                });
+            }
+            dealt_pack.insert(pile_name, deck.split_off(deck.len() - needed));
+        }
+        leftovers.insert(pile_name, deck);
+    }
+
+    let mut swap_random = seeded_rng(seed, "swaps");
+    let mut ratings: Vec<Rating> = dealt.iter().map(|p| rate(p, options.metric)).collect();
+    let mut swaps: usize = 0;
+    while swaps < options.max_swaps && ratings.len() > 1 {
+        let target = average(&ratings);
+        let (lowest, highest) = extremes(&ratings);
+        let current = (ratings[highest] - target).max(target - ratings[lowest]);
+        if current <= options.tolerance {
+            break;
+        }
+
+        let mut candidates: Vec<(&P, usize, usize)> = dealt[highest]
+            .iter()
+            .filter_map(|(pile_name, high_cards)| {
+                dealt[lowest]
+                    .get(pile_name)
+                    .map(|low_cards| (pile_name, high_cards.len(), low_cards.len()))
+            })
+            .flat_map(|(pile_name, high_count, low_count)| {
+                (0..high_count).flat_map(move |h| (0..low_count).map(move |l| (*pile_name, h, l)))
+            })
+            .collect();
+        // The packs are hash maps, so the candidates are sorted first for the seed to give the same swaps every time.
+        candidates.sort_unstable();
+        candidates.shuffle(&mut swap_random);
+
+        let mut best: Option<((&P, usize, usize), Rating, Rating)> = None;
+        let mut best_deviation = current;
+        for (pile_name, h, l) in candidates {
+            let mut high_pack = dealt[highest].clone();
+            let mut low_pack = dealt[lowest].clone();
+            let high_card = high_pack.get(pile_name).unwrap()[h];
+            let low_card = low_pack.get(pile_name).unwrap()[l];
+            high_pack.get_mut(pile_name).unwrap()[h] = low_card;
+            low_pack.get_mut(pile_name).unwrap()[l] = high_card;
+            let high_rating = rate(&high_pack, options.metric);
+            let low_rating = rate(&low_pack, options.metric);
+            let mut swapped_ratings = ratings.clone();
+            swapped_ratings[highest] = high_rating;
+            swapped_ratings[lowest] = low_rating;
+            let swapped_target = average(&swapped_ratings);
+            let deviation = (high_rating - swapped_target)
+                .abs()
+                .max((low_rating - swapped_target).abs());
+            if deviation < best_deviation {
+                best_deviation = deviation;
+                best = Some(((pile_name, h, l), high_rating, low_rating));
+            }
+        }
+
+        match best {
+            None => break,
+            Some(((pile_name, h, l), high_rating, low_rating)) => {
+                let high_card = dealt[highest][pile_name][h];
+                let low_card = dealt[lowest][pile_name][l];
+                dealt[highest].get_mut(pile_name).unwrap()[h] = low_card;
+                dealt[lowest].get_mut(pile_name).unwrap()[l] = high_card;
+                ratings[highest] = high_rating;
+                ratings[lowest] = low_rating;
+                swaps += 1;
+            }
+        }
+    }
+
+    let target = average(&ratings);
+    let (lowest, highest) = extremes(&ratings);
+    let spread = ratings
+        .get(highest)
+        .zip(ratings.get(lowest))
+        .map_or(0.0, |(high, low)| high - low);
+    Ok(Balanced {
+        packs: dealt
+            .into_iter()
+            .zip(ratings)
+            .map(|(cards, rating)| RatedPack {
+                cards,
+                rating,
+                deviation: rating - target,
+            })
+            .collect(),
+        leftovers,
+        average: target,
+        spread,
+        swaps,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use proptest::collection::{hash_map, vec};
+    use proptest::prelude::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use crate::balance::BalancingErrors::NotEnoughCards;
+    use crate::balance::{balance, BalanceMetric, BalanceOptions, RatedCard};
+    use crate::distribution_shuffle::{shuffle, Pack, Pile};
+
+    prop_compose! {
+        fn arb_cards
+            ()
+            (cards in hash_map("[A-Z][a-z]{0,8}", vec(0f64..10f64, 0..60), 1..8))
+            -> HashMap<String, Vec<RatedCard<usize>>>{
+            cards
+                .into_iter()
+                .map(|(pile, ratings)| {
+                    let rated = ratings
+                        .into_iter()
+                        .enumerate()
+                        .map(|(name, rating)| RatedCard { name, rating })
+                        .collect();
+                    (pile, rated)
+                })
+                .collect()
+        }
+    }
+
+    fn options(max_swaps: usize, tolerance: f64) -> BalanceOptions {
+        BalanceOptions {
+            metric: BalanceMetric::Total,
+            tolerance,
+            max_swaps,
+        }
+    }
+
+    #[test]
+    fn not_enough_cards() {
+        let green = String::from("Green");
+        let blue = String::from("Blue");
+        let packs = vec![Pack {
+            card_sources: HashMap::from([(&green, 2), (&blue, 1)]),
+        }];
+        let mut cards = HashMap::from([(
+            green.clone(),
+            vec![RatedCard {
+                name: 0,
+                rating: 1.0,
+            }],
+        )]);
+        let mut rng = StdRng::seed_from_u64(1);
+        assert_eq!(
+            Err(NotEnoughCards {
+                pile: blue.clone(),
+                needed: 1,
+                available: 0
+            }),
+            balance(&packs, &cards, &options(0, 0.0), &mut rng)
+        );
+        cards.insert(blue.clone(), Vec::new());
+        assert_eq!(
+            Err(NotEnoughCards {
+                pile: blue.clone(),
+                needed: 1,
+                available: 0
+            }),
+            balance(&packs, &cards, &options(0, 0.0), &mut rng)
+        );
+        cards.get_mut(&blue).unwrap().push(RatedCard {
+            name: 1,
+            rating: 1.0,
+        });
+        assert_eq!(
+            Err(NotEnoughCards {
+                pile: green.clone(),
+                needed: 2,
+                available: 1
+            }),
+            balance(&packs, &cards, &options(0, 0.0), &mut rng)
+        );
+    }
+
+    // Ratings tie a lot, so any hash map order would pick other swaps between runs.
+    #[test]
+    fn same_seed_same_packs() {
+        let cards: HashMap<String, Vec<RatedCard<usize>>> = ["Red", "Green", "Blue"]
+            .iter()
+            .map(|pile| {
+                let rated = (0..30)
+                    .map(|name| RatedCard {
+                        name,
+                        rating: (name % 3) as f64,
+                    })
+                    .collect();
+                (pile.to_string(), rated)
+            })
+            .collect();
+        let piles: HashMap<String, Pile> = cards
+            .iter()
+            .map(|(name, c)| {
+                (
+                    name.clone(),
+                    Pile {
+                        cards: c.len(),
+                        randomness: 0.5,
+                    },
+                )
+            })
+            .collect();
+        let packs = shuffle(&piles, 6, &mut StdRng::seed_from_u64(7)).unwrap();
+        let balance_once = || {
+            balance(
+                &packs,
+                &cards,
+                &options(100, 0.0),
+                &mut StdRng::seed_from_u64(7),
+            )
+            .unwrap()
+        };
+        let first = balance_once();
+        for _ in 0..10 {
+            assert_eq!(first, balance_once());
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn keeps_distribution(
+            cards in arb_cards(),
+            randomness in 0f64..=1f64,
+            pack_size in 1usize..20,
+            seed in any::<u64>(),
+        ){
+            let piles: HashMap<String, Pile> = cards
+                .iter()
+                .map(|(name, c)| (name.clone(), Pile { cards: c.len(), randomness }))
+                .collect();
+            let mut rng = StdRng::seed_from_u64(seed);
+            let packs = shuffle(&piles, pack_size, &mut rng).unwrap();
+            let balanced = balance(&packs, &cards, &options(100, 0.0), &mut rng).unwrap();
+
+            assert_eq!(packs.len(), balanced.packs.len());
+            for (pack, rated) in packs.iter().zip(balanced.packs.iter()) {
+                for (pile_name, count) in pack.card_sources.iter() {
+                    assert_eq!(*count, rated.cards[pile_name].len());
+                }
+            }
+            for (pile_name, pile_cards) in cards.iter() {
+                let mut used: Vec<usize> = balanced
+                    .packs
+                    .iter()
+                    .filter_map(|p| p.cards.get(pile_name))
+                    .chain(balanced.leftovers.get(pile_name))
+                    .flatten()
+                    .map(|c| c.name)
+                    .collect();
+                used.sort_unstable();
+                assert_eq!((0..pile_cards.len()).collect::<Vec<usize>>(), used);
+            }
+        }
+
+        #[test]
+        fn narrows_spread(
+            cards in arb_cards(),
+            pack_size in 1usize..20,
+            seed in any::<u64>(),
+        ){
+            let piles: HashMap<String, Pile> = cards
+                .iter()
+                .map(|(name, c)| (name.clone(), Pile { cards: c.len(), randomness: 0.0 }))
+                .collect();
+            let packs = shuffle(&piles, pack_size, &mut StdRng::seed_from_u64(seed)).unwrap();
+            let unbalanced = balance(&packs, &cards, &options(0, 0.0), &mut StdRng::seed_from_u64(seed)).unwrap();
+            let balanced = balance(&packs, &cards, &options(100, 0.0), &mut StdRng::seed_from_u64(seed)).unwrap();
+            let untouched = balance(&packs, &cards, &options(100, f64::MAX), &mut StdRng::seed_from_u64(seed)).unwrap();
+
+            assert_eq!(0, unbalanced.swaps);
+            assert_eq!(0, untouched.swaps);
+            assert!(balanced.spread <= unbalanced.spread + 1e-9);
+        }
+    }
+}
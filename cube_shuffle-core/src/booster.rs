@@ -0,0 +1,248 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use rand::prelude::SliceRandom;
+use rand::{Rng, RngCore};
+use serde::{Deserialize, Serialize};
+
+use crate::booster::CollationErrors::{EmptyTemplate, ExhaustedSlot, UnknownPile};
+use crate::distribution_shuffle::{Pack, Pile};
+use crate::seed::seeded_rng;
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Slot<P> {
+    pub piles: Vec<P>,
+    pub fallbacks: Vec<P>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Template<P> {
+    pub slots: Vec<Slot<P>>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub enum CollationErrors {
+    EmptyTemplate,
+    UnknownPile { slot: usize },
+    ExhaustedSlot { pack: usize, slot: usize },
+}
+
+fn draw<'a, P>(
+    sources: &[&'a P],
+    remaining: &mut HashMap<&'a P, usize>,
+    random: &mut impl RngCore,
+) -> Option<&'a P>
+where
+    P: Eq + Hash,
+{
+    let available: usize = sources.iter().map(|s| remaining[s]).sum();
+    if available == 0 {
+        return None;
+    }
+    let mut pick: usize = random.gen_range(0..available);
+    for source in sources {
+        let cards = remaining.get_mut(source).unwrap();
+        if pick < *cards {
+            *cards -= 1;
+            return Some(*source);
+        }
+        pick -= *cards;
+    }
+    None
+}
+
+// Slots are filled in template order for all packs before moving on to the next slot.
+// Slots drawing from several piles pick a pile weighted by the cards it has left, the randomness of piles is not used.
+// Each slot visits the packs in its own random order, so the packs left with fallbacks are not always the last ones.
+pub fn collate<'a, P>(
+    piles: &'a HashMap<P, Pile>,
+    template: &Template<P>,
+    pack_count: usize,
+    random: &mut impl RngCore,
+) -> Result<Vec<Pack<&'a P>>, CollationErrors>
+where
+    P: Eq + Hash + Serialize,
+{
+    if template.slots.is_empty() {
+        return Err(EmptyTemplate);
+    }
+
+    let mut slots: Vec<(Vec<&P>, Vec<&P>)> = Vec::new();
+    for (slot_index, slot) in template.slots.iter().enumerate() {
+        let lookup = |names: &Vec<P>| -> Result<Vec<&'a P>, CollationErrors> {
+            names
+                .iter()
+                .map(|name| {
+                    piles
+                        .get_key_value(name)
+                        .map(|(key, _)| key)
+                        .ok_or(UnknownPile { slot: slot_index })
+                })
+                .collect()
+        };
+        slots.push((lookup(&slot.piles)?, lookup(&slot.fallbacks)?));
+    }
+
+    let seed = random.next_u64();
+    let mut remaining: HashMap<&P, usize> = piles.iter().map(|(k, p)| (k, p.cards)).collect();
+    let mut packs: Vec<Pack<&P>> = vec![
+        Pack {
+            card_sources: HashMap::new()
+        };
+        pack_count
+    ];
+    for (slot_index, (slot_piles, fallbacks)) in slots.iter().enumerate() {
+        let mut slot_random = seeded_rng(seed, &("slot", slot_index));
+        let mut order: Vec<usize> = (0..pack_count).collect();
+        order.shuffle(&mut slot_random);
+        for pack_index in order {
+            let source = draw(slot_piles, &mut remaining, &mut slot_random)
+                .or_else(|| draw(fallbacks, &mut remaining, &mut slot_random))
+                .ok_or(ExhaustedSlot {
+                    pack: pack_index,
+                    slot: slot_index,
+                })?;
+            *packs[pack_index].card_sources.entry(source).or_insert(0) += 1;
+        }
+    }
+
+    Ok(packs)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use proptest::prelude::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use crate::booster::CollationErrors::{EmptyTemplate, ExhaustedSlot, UnknownPile};
+    use crate::booster::{collate, Slot, Template};
+    use crate::distribution_shuffle::Pile;
+
+    fn pile(cards: usize) -> Pile {
+        Pile {
+            cards,
+            randomness: 0.0,
+        }
+    }
+
+    fn slot(piles: &[&'static str], fallbacks: &[&'static str]) -> Slot<&'static str> {
+        Slot {
+            piles: piles.to_vec(),
+            fallbacks: fallbacks.to_vec(),
+        }
+    }
+
+    fn retail() -> Template<&'static str> {
+        let mut slots = vec![slot(&["Rare", "Mythic"], &["Uncommon"])];
+        slots.extend((0..3).map(|_| slot(&["Uncommon"], &[])));
+        slots.extend((0..10).map(|_| slot(&["Common"], &[])));
+        Template { slots }
+    }
+
+    proptest! {
+        #[test]
+        fn follows_template(
+            pack_count in 0usize..50,
+            mythics in 0usize..20,
+            spare_cards in 0usize..100,
+            seed in any::<u64>(),
+        ){
+            let piles: HashMap<&str, Pile> = HashMap::from([
+                ("Mythic", pile(mythics)),
+                ("Rare", pile(pack_count)),
+                ("Uncommon", pile(pack_count * 3 + spare_cards)),
+                ("Common", pile(pack_count * 10 + spare_cards)),
+            ]);
+            let mut rng = StdRng::seed_from_u64(seed);
+            let packs = collate(&piles, &retail(), pack_count, &mut rng).unwrap();
+
+            assert_eq!(pack_count, packs.len());
+            for pack in packs.iter() {
+                let count = |pile: &str| pack.card_sources.get(&pile).copied().unwrap_or(0);
+                assert_eq!(1, count("Rare") + count("Mythic"));
+                assert_eq!(3, count("Uncommon"));
+                assert_eq!(10, count("Common"));
+            }
+
+            for (name, pile) in piles.iter() {
+                let used: usize = packs.iter().filter_map(|p| p.card_sources.get(name)).sum();
+                assert!(used <= pile.cards);
+            }
+        }
+
+        #[test]
+        fn uses_fallbacks(
+            pack_count in 1usize..50,
+            rares in 0usize..50,
+            seed in any::<u64>(),
+        ){
+            let rares = rares.min(pack_count);
+            let piles: HashMap<&str, Pile> = HashMap::from([
+                ("Rare", pile(rares)),
+                ("Mythic", pile(0)),
+                ("Uncommon", pile(pack_count * 4)),
+                ("Common", pile(pack_count * 10)),
+            ]);
+            let mut rng = StdRng::seed_from_u64(seed);
+            let packs = collate(&piles, &retail(), pack_count, &mut rng).unwrap();
+
+            let uncommons: usize = packs.iter().filter_map(|p| p.card_sources.get(&"Uncommon")).sum();
+            assert_eq!(pack_count * 3 + (pack_count - rares), uncommons);
+        }
+    }
+
+    #[test]
+    fn invalid_templates() {
+        let piles: HashMap<&str, Pile> = HashMap::from([("Common", pile(10))]);
+        let mut rng = StdRng::seed_from_u64(0);
+        assert_eq!(
+            Err(EmptyTemplate),
+            collate(&piles, &Template { slots: vec![] }, 1, &mut rng)
+        );
+        assert_eq!(
+            Err(UnknownPile { slot: 1 }),
+            collate(
+                &piles,
+                &Template {
+                    slots: vec![slot(&["Common"], &[]), slot(&["Common"], &["Rare"])]
+                },
+                1,
+                &mut rng
+            )
+        );
+        assert!(matches!(
+            collate(
+                &piles,
+                &Template {
+                    slots: vec![slot(&["Common"], &[]); 2]
+                },
+                6,
+                &mut rng
+            ),
+            Err(ExhaustedSlot { slot: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn spreads_fallbacks() {
+        let piles: HashMap<&str, Pile> = HashMap::from([
+            ("Rare", pile(20)),
+            ("Mythic", pile(0)),
+            ("Uncommon", pile(160)),
+            ("Common", pile(400)),
+        ]);
+        let mut rng = StdRng::seed_from_u64(1);
+        let packs = collate(&piles, &retail(), 40, &mut rng).unwrap();
+        let fallbacks: Vec<usize> = packs
+            .iter()
+            .enumerate()
+            .filter(|(_, p)| !p.card_sources.contains_key(&"Rare"))
+            .map(|(index, _)| index)
+            .collect();
+        assert_eq!(20, fallbacks.len());
+        assert!(fallbacks.iter().any(|index| *index < 20));
+    }
+}
@@ -0,0 +1,229 @@
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_256};
+
+use crate::commit_reveal::CommitRevealErrors::{
+    CommitmentMismatch, DuplicatePlayer, EmptyTranscript, MissingReveal, RevealStarted,
+    UnknownPlayer,
+};
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Contribution {
+    pub player: String,
+    pub commitment: String,
+    #[serde(default)]
+    pub nonce: Option<String>,
+    #[serde(default)]
+    pub secret: Option<String>,
+}
+
+// Every player publishes the hash of a secret before any secret is revealed, so nobody can pick their secret
+// after seeing the others'. A random nonce is hashed along with the secret, so short secrets can not be guessed
+// from the commitment.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Transcript {
+    pub contributions: Vec<Contribution>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub enum CommitRevealErrors {
+    EmptyTranscript,
+    DuplicatePlayer { player: String },
+    UnknownPlayer { player: String },
+    RevealStarted,
+    CommitmentMismatch { player: String },
+    MissingReveal { player: String },
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+pub fn nonce(random: &mut impl RngCore) -> String {
+    let mut bytes = [0u8; 16];
+    random.fill_bytes(&mut bytes);
+    hex(&bytes)
+}
+
+pub fn commitment(nonce: &str, secret: &str) -> String {
+    let mut hasher = Sha3_256::new();
+    hasher.update((nonce.len() as u64).to_le_bytes());
+    hasher.update(nonce.as_bytes());
+    hasher.update(secret.as_bytes());
+    hex(&hasher.finalize())
+}
+
+impl Transcript {
+    pub fn commit(&mut self, player: String, commitment: String) -> Result<(), CommitRevealErrors> {
+        if self.contributions.iter().any(|c| c.secret.is_some()) {
+            return Err(RevealStarted);
+        }
+        if self.contributions.iter().any(|c| c.player == player) {
+            return Err(DuplicatePlayer { player });
+        }
+        self.contributions.push(Contribution {
+            player,
+            commitment: commitment.to_lowercase(),
+            nonce: None,
+            secret: None,
+        });
+        Ok(())
+    }
+
+    pub fn reveal(
+        &mut self,
+        player: &str,
+        nonce: String,
+        secret: String,
+    ) -> Result<(), CommitRevealErrors> {
+        let contribution = self
+            .contributions
+            .iter_mut()
+            .find(|c| c.player == player)
+            .ok_or_else(|| UnknownPlayer {
+                player: player.to_string(),
+            })?;
+        if commitment(&nonce, &secret) != contribution.commitment.to_lowercase() {
+            return Err(CommitmentMismatch {
+                player: player.to_string(),
+            });
+        }
+        contribution.nonce = Some(nonce);
+        contribution.secret = Some(secret);
+        Ok(())
+    }
+
+    // Verifies every reveal against its commitment and hashes all secrets into the seed.
+    // Secrets are ordered by commitment, so the order players joined in does not matter.
+    pub fn combine(&self) -> Result<String, CommitRevealErrors> {
+        if self.contributions.is_empty() {
+            return Err(EmptyTranscript);
+        }
+        let mut revealed: Vec<(String, &str)> = Vec::new();
+        for contribution in self.contributions.iter() {
+            let (nonce, secret) = contribution
+                .nonce
+                .as_deref()
+                .zip(contribution.secret.as_deref())
+                .ok_or_else(|| MissingReveal {
+                    player: contribution.player.clone(),
+                })?;
+            let hash = commitment(nonce, secret);
+            if hash != contribution.commitment.to_lowercase() {
+                return Err(CommitmentMismatch {
+                    player: contribution.player.clone(),
+                });
+            }
+            revealed.push((hash, secret));
+        }
+        revealed.sort();
+
+        let mut hasher = Sha3_256::new();
+        for (_, secret) in revealed {
+            hasher.update((secret.len() as u64).to_le_bytes());
+            hasher.update(secret.as_bytes());
+        }
+        Ok(hex(&hasher.finalize()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use crate::commit_reveal::CommitRevealErrors::{
+        CommitmentMismatch, MissingReveal, RevealStarted,
+    };
+    use crate::commit_reveal::{commitment, nonce, Transcript};
+    use crate::seed::seeded_rng;
+
+    // Each player keeps their nonce whatever order they join in.
+    fn transcript(players: &[(&str, &str)]) -> Transcript {
+        let nonces: Vec<String> = players
+            .iter()
+            .map(|(player, _)| nonce(&mut seeded_rng(1, *player)))
+            .collect();
+        let mut transcript = Transcript::default();
+        for ((player, secret), nonce) in players.iter().zip(&nonces) {
+            transcript
+                .commit(player.to_string(), commitment(nonce, secret))
+                .unwrap();
+        }
+        for ((player, secret), nonce) in players.iter().zip(nonces) {
+            transcript
+                .reveal(player, nonce, secret.to_string())
+                .unwrap();
+        }
+        transcript
+    }
+
+    #[test]
+    fn order_independent() {
+        let first = transcript(&[("Alice", "apple"), ("Bob", "banana")]);
+        let second = transcript(&[("Bob", "banana"), ("Alice", "apple")]);
+        assert_eq!(first.combine(), second.combine());
+        assert_ne!(
+            first.combine(),
+            transcript(&[("Alice", "apple"), ("Bob", "cherry")]).combine()
+        );
+    }
+
+    #[test]
+    fn salted_commitment() {
+        let mut random = StdRng::seed_from_u64(1);
+        let first = nonce(&mut random);
+        let second = nonce(&mut random);
+        assert_eq!(32, first.len());
+        assert_ne!(commitment(&first, "7"), commitment(&second, "7"));
+        // The length prefix keeps the nonce and secret from shifting into each other.
+        assert_ne!(commitment("ab", "c"), commitment("a", "bc"));
+    }
+
+    #[test]
+    fn wrong_secret() {
+        let mut transcript = Transcript::default();
+        transcript
+            .commit(String::from("Alice"), commitment("salt", "apple"))
+            .unwrap();
+        assert_eq!(
+            Err(CommitmentMismatch {
+                player: String::from("Alice")
+            }),
+            transcript.reveal("Alice", String::from("salt"), String::from("pear"))
+        );
+        assert_eq!(
+            Err(CommitmentMismatch {
+                player: String::from("Alice")
+            }),
+            transcript.reveal("Alice", String::from("pepper"), String::from("apple"))
+        );
+        assert_eq!(
+            Err(MissingReveal {
+                player: String::from("Alice")
+            }),
+            transcript.combine()
+        );
+    }
+
+    #[test]
+    fn late_commit() {
+        let mut transcript = transcript(&[("Alice", "apple")]);
+        assert_eq!(
+            Err(RevealStarted),
+            transcript.commit(String::from("Bob"), commitment("salt", "banana"))
+        );
+    }
+
+    #[test]
+    fn tampered_transcript() {
+        let mut transcript = transcript(&[("Alice", "apple"), ("Bob", "banana")]);
+        transcript.contributions[1].secret = Some(String::from("cherry"));
+        assert_eq!(
+            Err(CommitmentMismatch {
+                player: String::from("Bob")
+            }),
+            transcript.combine()
+        );
+    }
+}
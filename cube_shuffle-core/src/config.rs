@@ -0,0 +1,113 @@
+use std::collections::HashMap;
+
+use rand::prelude::StdRng;
+use rand::SeedableRng;
+use serde::{Deserialize, Serialize};
+
+use crate::config::ConfigErrors::{EmptyPacks, InvalidRandomness};
+use crate::distribution_shuffle::{shuffle, Pack, Pile, ShufflingErrors};
+use crate::seed::parse_seed;
+
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+fn current_version() -> String {
+    String::from(VERSION)
+}
+
+// Configs from before versions were saved, which may have been shuffled by any older version.
+fn unknown_version() -> String {
+    String::from("unknown")
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct Config {
+    pub seed: String,
+    pub pack_size: usize,
+    pub piles: HashMap<String, Pile>,
+    // The version of CubeShuffle which shuffled the config, as the same seed may give other packs in other versions.
+    #[serde(default = "unknown_version")]
+    pub version: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ConfigErrors {
+    EmptyPacks,
+    InvalidRandomness { pile: String },
+}
+
+impl Config {
+    pub fn new(seed: String, pack_size: usize, piles: HashMap<String, Pile>) -> Self {
+        Self {
+            seed,
+            pack_size,
+            piles,
+            version: current_version(),
+        }
+    }
+
+    // Catches values which deserialize fine but could never have been entered, such as from hand edited files.
+    pub fn validate(&self) -> Result<(), ConfigErrors> {
+        if self.pack_size == 0 {
+            return Err(EmptyPacks);
+        }
+        let mut pile_names: Vec<&String> = self.piles.keys().collect();
+        pile_names.sort();
+        for pile_name in pile_names {
+            if !(0.0..=1.0).contains(&self.piles[pile_name].randomness) {
+                return Err(InvalidRandomness {
+                    pile: pile_name.clone(),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    pub fn shuffle(&self) -> Result<Vec<Pack<&String>>, ShufflingErrors> {
+        let mut rng = StdRng::seed_from_u64(parse_seed(&self.seed));
+        shuffle(&self.piles, self.pack_size, &mut rng)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::config::Config;
+    use crate::config::ConfigErrors::{EmptyPacks, InvalidRandomness};
+    use crate::distribution_shuffle::Pile;
+
+    #[test]
+    fn validate() {
+        let mut config = Config::new(
+            String::from("1"),
+            15,
+            HashMap::from([(
+                String::from("Red"),
+                Pile {
+                    cards: 10,
+                    randomness: 0.5,
+                },
+            )]),
+        );
+        assert_eq!(Ok(()), config.validate());
+        config.piles.get_mut("Red").unwrap().randomness = 1.5;
+        assert_eq!(
+            Err(InvalidRandomness {
+                pile: String::from("Red")
+            }),
+            config.validate()
+        );
+        config.pack_size = 0;
+        assert_eq!(Err(EmptyPacks), config.validate());
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let config = Config::new(String::from("Friday"), 15, HashMap::new());
+        let json = serde_json::to_string(&config).unwrap();
+        assert_eq!(config, serde_json::from_str(&json).unwrap());
+        let old: Config =
+            serde_json::from_str(r#"{"seed":"1","pack_size":15,"piles":{}}"#).unwrap();
+        assert_eq!("unknown", old.version);
+    }
+}
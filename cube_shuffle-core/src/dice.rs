@@ -0,0 +1,153 @@
+use serde::{Deserialize, Serialize};
+
+use crate::dice::DiceErrors::{InvalidDie, InvalidRoll, UnreadableRoll};
+
+pub const SEED_BITS: u32 = 64;
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub enum DiceErrors {
+    InvalidDie { sides: u32 },
+    InvalidRoll { roll: u32, sides: u32 },
+    UnreadableRoll { roll: String },
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DiceSeed {
+    // Only set once the rolls add up to all the bits of a seed.
+    pub seed: Option<u64>,
+    pub bits: u32,
+    pub rolls_needed: usize,
+}
+
+// The sides of the die are split into blocks with the sizes of its binary digits, such as 4 and 2 for a d6.
+// A roll within a block of 2^k sides gives k uniform bits, so no roll is biased towards any seed.
+fn roll_bits(sides: u32, roll: u32) -> (u64, u32) {
+    let mut value = roll - 1;
+    for k in (0..u32::BITS).rev() {
+        let block = 1u32 << k;
+        if sides & block == 0 {
+            continue;
+        }
+        if value < block {
+            return (u64::from(value), k);
+        }
+        value -= block;
+    }
+    (0, 0)
+}
+
+fn expected_bits(sides: u32) -> f64 {
+    (0..u32::BITS)
+        .filter(|k| sides & (1 << k) != 0)
+        .map(|k| f64::from(1u32 << k) / f64::from(sides) * f64::from(k))
+        .sum()
+}
+
+pub fn parse_rolls(text: &str) -> Result<Vec<u32>, DiceErrors> {
+    text.split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|r| !r.is_empty())
+        .map(|r| {
+            r.parse::<u32>().map_err(|_| UnreadableRoll {
+                roll: r.to_string(),
+            })
+        })
+        .collect()
+}
+
+pub fn dice_seed(sides: u32, rolls: &[u32]) -> Result<DiceSeed, DiceErrors> {
+    if sides < 2 {
+        return Err(InvalidDie { sides });
+    }
+    let mut seed: u64 = 0;
+    let mut bits: u32 = 0;
+    for roll in rolls.iter() {
+        if *roll == 0 || *roll > sides {
+            return Err(InvalidRoll { roll: *roll, sides });
+        }
+        if bits == SEED_BITS {
+            continue;
+        }
+        let (value, count) = roll_bits(sides, *roll);
+        let count = count.min(SEED_BITS - bits);
+        if count > 0 {
+            seed = (seed << count) | (value & (u64::MAX >> (u64::BITS - count)));
+            bits += count;
+        }
+    }
+    let rolls_needed = (f64::from(SEED_BITS - bits) / expected_bits(sides)).ceil() as usize;
+    Ok(DiceSeed {
+        seed: (bits == SEED_BITS).then_some(seed),
+        bits,
+        rolls_needed,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::collection::vec;
+    use proptest::prelude::*;
+
+    use crate::dice::DiceErrors::{InvalidDie, InvalidRoll, UnreadableRoll};
+    use crate::dice::{dice_seed, parse_rolls, roll_bits, DiceSeed};
+
+    #[test]
+    fn coin_flips() {
+        let mut rolls = vec![2; 63];
+        rolls.push(1);
+        assert_eq!(
+            Ok(DiceSeed {
+                seed: Some(u64::MAX - 1),
+                bits: 64,
+                rolls_needed: 0
+            }),
+            dice_seed(2, &rolls)
+        );
+    }
+
+    #[test]
+    fn six_sided() {
+        assert_eq!((3, 2), roll_bits(6, 4));
+        assert_eq!((0, 1), roll_bits(6, 5));
+        assert_eq!((1, 1), roll_bits(6, 6));
+        let partial = dice_seed(6, &[1, 6, 3]).unwrap();
+        assert_eq!(None, partial.seed);
+        assert_eq!(5, partial.bits);
+        assert_eq!(36, partial.rolls_needed);
+    }
+
+    #[test]
+    fn invalid_rolls() {
+        assert_eq!(Err(InvalidDie { sides: 1 }), dice_seed(1, &[1]));
+        assert_eq!(
+            Err(InvalidRoll { roll: 7, sides: 6 }),
+            dice_seed(6, &[1, 7])
+        );
+        assert_eq!(
+            Err(UnreadableRoll {
+                roll: String::from("x")
+            }),
+            parse_rolls("1, 2 x")
+        );
+        assert_eq!(Ok(vec![1, 2, 3]), parse_rolls(" 1,2\n3 "));
+    }
+
+    proptest! {
+        #[test]
+        fn enough_rolls(sides in 2u32..=100, rolls in vec(any::<u32>(), 0..300)) {
+            let rolls: Vec<u32> = rolls.into_iter().map(|r| r % sides + 1).collect();
+            let seed = dice_seed(sides, &rolls).unwrap();
+            assert_eq!(seed.seed.is_some(), seed.bits == 64);
+            assert_eq!(seed.rolls_needed == 0, seed.bits == 64);
+        }
+
+        #[test]
+        fn blocks_cover_all_sides(sides in 2u32..=1000) {
+            let mut seen = std::collections::HashSet::new();
+            for roll in 1..=sides {
+                let (value, bits) = roll_bits(sides, roll);
+                assert!(value < 1 << bits);
+                assert!(seen.insert((value, bits)));
+            }
+        }
+    }
+}
@@ -1,12 +1,14 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::HashMap;
 use std::hash::Hash;
 
 use parse_display::{Display, FromStr};
 use rand::prelude::SliceRandom;
+use rand::seq::index::sample;
 use rand::{Rng, RngCore};
 use serde::{Deserialize, Serialize};
 
 use crate::distribution_shuffle::ShufflingErrors::{CardOverflow, EmptyPacks};
+use crate::seed::{seeded_rng, Stream};
 
 pub type Odds = f64;
 
@@ -34,13 +36,128 @@ pub enum ShufflingErrors {
     },
 }
 
-pub fn shuffle<'a, P>(
+// Returns how many cards of each pile are left over, in the order of the given piles.
+// Every card draws its priority from the stream of its own pile, so a pile keeps the priorities of its cards when others change.
+// Cards of randomized piles are left over first, which keeps piles with 0% randomness whole whenever possible.
+fn leftovers<P>(piles: &[(&P, &Pile)], leftover_count: usize, seed: u64) -> Vec<usize>
+where
+    P: Stream,
+{
+    let mut cards: Vec<(bool, u64, usize)> = piles
+        .iter()
+        .enumerate()
+        .flat_map(|(pile_index, (pile_name, pile))| {
+            let mut pile_random = seeded_rng(seed, &("leftovers", *pile_name));
+            let fixed = pile.randomness == 0.0;
+            (0..pile.cards).map(move |_| (fixed, pile_random.next_u64(), pile_index))
+        })
+        .collect();
+    cards.sort_unstable();
+
+    let mut pile_leftovers: Vec<usize> = vec![0; piles.len()];
+    for (_, _, pile_index) in cards.into_iter().take(leftover_count) {
+        pile_leftovers[pile_index] += 1;
+    }
+    pile_leftovers
+}
+
+// Spreads the cards of each pile as evenly as possible over the packs.
+// Piles which can't be divided evenly place their extra cards in the packs with the fewest extra cards so far,
+// which keeps every pack the same size.
+fn spread<P>(piles: &[(&P, usize)], pack_count: usize, seed: u64) -> Vec<Vec<usize>>
+where
+    P: Stream,
+{
+    let mut fewest_extras: Vec<usize> = (0..pack_count).collect();
+    let mut most_extras: Vec<usize> = Vec::new();
+    piles
+        .iter()
+        .map(|(pile_name, cards)| {
+            let mut pack_cards: Vec<usize> = vec![cards / pack_count; pack_count];
+            let mut extras: usize = cards % pack_count;
+            if extras >= fewest_extras.len() {
+                extras -= fewest_extras.len();
+                for pack in fewest_extras.iter() {
+                    pack_cards[*pack] += 1;
+                }
+                fewest_extras.append(&mut most_extras);
+                fewest_extras.sort_unstable();
+            }
+
+            let mut picks: Vec<usize> = sample(
+                &mut seeded_rng(seed, &("spread", *pile_name)),
+                fewest_extras.len(),
+                extras,
+            )
+            .into_vec();
+            picks.sort_unstable_by(|a, b| b.cmp(a));
+            for pick in picks {
+                let pack = fewest_extras.swap_remove(pick);
+                pack_cards[pack] += 1;
+                most_extras.push(pack);
+            }
+            pack_cards
+        })
+        .collect()
+}
+
+// Spreads a pile on its own, its extra cards go to packs picked only by the stream of the pile.
+// Adding cards to the pile only adds packs to the picked ones.
+fn spread_alone<P>(pile_name: &P, cards: usize, pack_count: usize, seed: u64) -> Vec<usize>
+where
+    P: Stream,
+{
+    let mut pack_cards: Vec<usize> = vec![cards / pack_count; pack_count];
+    let mut packs: Vec<usize> = (0..pack_count).collect();
+    let (picked, _) = packs.partial_shuffle(
+        &mut seeded_rng(seed, &("spread", pile_name)),
+        cards % pack_count,
+    );
+    for pack in picked.iter() {
+        pack_cards[*pack] += 1;
+    }
+    pack_cards
+}
+
+// Fills the room piles with 0% randomness left in each pack with the cards of the randomized piles.
+// Each pile takes the next run of slots, which go round the packs so every pile is spread evenly.
+fn fill<P>(piles: &[(&P, usize)], room: &[usize], seed: u64) -> Vec<Vec<usize>> {
+    let mut packs: Vec<usize> = (0..room.len()).collect();
+    packs.shuffle(&mut seeded_rng(seed, "fill"));
+    let rounds = room.iter().copied().max().unwrap_or(0);
+    let mut slots = (0..rounds).flat_map(|round| {
+        packs
+            .iter()
+            .copied()
+            .filter(move |pack| room[*pack] > round)
+            .collect::<Vec<usize>>()
+    });
+    piles
+        .iter()
+        .map(|(_, cards)| {
+            let mut pack_cards: Vec<usize> = vec![0; room.len()];
+            for pack in slots.by_ref().take(*cards) {
+                pack_cards[pack] += 1;
+            }
+            pack_cards
+        })
+        .collect()
+}
+
+pub(crate) type PileLayout<'a, P> = (&'a P, &'a Pile, Vec<usize>);
+
+// Orders the piles and spreads the cards which aren't left over evenly over the packs.
+// Piles with 0% randomness are spread on their own and the randomized piles fill the rest of every pack,
+// so editing one pile keeps the packs of piles with 0% randomness as long as the number of packs stays the same.
+// Only when the piles with 0% randomness can't be spread on their own are all piles spread together.
+// Returns the number of packs and the cards each pile contributes to each pack.
+pub(crate) fn layout<'a, P>(
     piles: &'a HashMap<P, Pile>,
     pack_size: usize,
-    random: &mut impl RngCore,
-) -> Result<Vec<Pack<&'a P>>, ShufflingErrors>
+    seed: u64,
+) -> Result<(usize, Vec<PileLayout<'a, P>>), ShufflingErrors>
 where
-    P: Eq + Hash + Serialize,
+    P: Eq + Hash + Ord + Stream,
 {
     if pack_size == 0 {
         return Err(EmptyPacks);
@@ -56,48 +173,96 @@ where
     }
 
     let pack_count: usize = card_count as usize / pack_size;
+    if pack_count == 0 {
+        return Ok((0, Vec::new()));
+    }
 
-    let pack_overflow: usize = card_count as usize % pack_size;
-    let overflow_cards: HashSet<usize> = if pack_overflow == 0 {
-        HashSet::new()
-    } else {
-        let mut cards: Vec<usize> = (0..(card_count as usize)).collect();
-        cards.shuffle(random);
-        cards.into_iter().take(pack_overflow as usize).collect()
-    };
+    let mut ordered_piles: Vec<(&P, &Pile)> = piles.iter().collect();
+    ordered_piles.sort_unstable_by_key(|(pile_name, _)| *pile_name);
 
-    let mut packs: Vec<HashMap<Option<&P>, usize>> = Vec::new();
-    for _ in 0..pack_count {
-        packs.push(HashMap::new())
-    }
+    let pile_leftovers = leftovers(&ordered_piles, card_count as usize % pack_size, seed);
+    let dealt_piles: Vec<(&P, usize)> = ordered_piles
+        .iter()
+        .zip(pile_leftovers)
+        .map(|((pile_name, pile), leftover)| (*pile_name, pile.cards - leftover))
+        .collect();
 
-    let mut card_index: usize = 0;
-    let mut randomized: Vec<&P> = Vec::new();
-    for (pile_name, pile) in piles {
-        let mut pile_modifier: usize = 0;
-        for c in 0..pile.cards {
-            if overflow_cards.contains(&card_index) {
-                card_index += 1;
-                pile_modifier += 1;
-                continue;
-            }
-            card_index += 1;
-            let skip: bool = random.gen_bool(pile.randomness);
-            if skip {
-                randomized.push(pile_name);
+    let mut spreads: Vec<Option<Vec<usize>>> = vec![None; dealt_piles.len()];
+    let mut room: Vec<usize> = vec![pack_size; pack_count];
+    let mut fits = true;
+    for (index, ((_, pile), (pile_name, cards))) in
+        ordered_piles.iter().zip(dealt_piles.iter()).enumerate()
+    {
+        if pile.randomness != 0.0 {
+            continue;
+        }
+        let pack_cards = spread_alone(*pile_name, *cards, pack_count, seed);
+        for (free, pack) in room.iter_mut().zip(pack_cards.iter()) {
+            match free.checked_sub(*pack) {
+                Some(left) => *free = left,
+                None => fits = false,
             }
+        }
+        spreads[index] = Some(pack_cards);
+    }
 
-            let pack_index: usize = (c - pile_modifier) % pack_count as usize;
-            *packs[pack_index]
-                .entry(if skip { None } else { Some(pile_name) })
-                .or_insert(0) += 1;
+    let spreads: Vec<Vec<usize>> = match fits {
+        true => {
+            let randomized: Vec<(&P, usize)> = dealt_piles
+                .iter()
+                .zip(spreads.iter())
+                .filter(|(_, spread)| spread.is_none())
+                .map(|(dealt, _)| *dealt)
+                .collect();
+            let mut filled = fill(&randomized, &room, seed).into_iter();
+            spreads
+                .into_iter()
+                .map(|spread| spread.unwrap_or_else(|| filled.next().unwrap()))
+                .collect()
         }
+        false => spread(&dealt_piles, pack_count, seed),
+    };
+
+    Ok((
+        pack_count,
+        ordered_piles
+            .into_iter()
+            .zip(spreads)
+            .map(|((pile_name, pile), pack_cards)| (pile_name, pile, pack_cards))
+            .collect(),
+    ))
+}
 
-        packs.shuffle(random);
-        packs.sort_by_key(|k| k.values().sum::<usize>());
+pub fn shuffle<'a, P>(
+    piles: &'a HashMap<P, Pile>,
+    pack_size: usize,
+    random: &mut impl RngCore,
+) -> Result<Vec<Pack<&'a P>>, ShufflingErrors>
+where
+    P: Eq + Hash + Ord + Serialize + Stream,
+{
+    // Every pile and stage draws from its own stream, so editing one pile leaves the others alone where possible.
+    let seed = random.next_u64();
+    let (pack_count, pile_layouts) = layout(piles, pack_size, seed)?;
+
+    let mut packs: Vec<HashMap<Option<&P>, usize>> = vec![HashMap::new(); pack_count];
+    let mut randomized: Vec<&P> = Vec::new();
+    for (pile_name, pile, pack_cards) in pile_layouts {
+        let mut pile_random = seeded_rng(seed, &("randomness", pile_name));
+        for (pack, cards) in packs.iter_mut().zip(pack_cards) {
+            for _ in 0..cards {
+                let skip: bool = pile_random.gen_bool(pile.randomness);
+                if skip {
+                    randomized.push(pile_name);
+                }
+                *pack
+                    .entry(if skip { None } else { Some(pile_name) })
+                    .or_insert(0) += 1;
+            }
+        }
     }
 
-    randomized.shuffle(random);
+    randomized.shuffle(&mut seeded_rng(seed, "randomized"));
     let finalized_packs: Vec<Pack<&P>> = packs
         .iter()
         .map(|incomplete_pack| {
@@ -129,7 +294,8 @@ mod tests {
     use rand::SeedableRng;
 
     use crate::distribution_shuffle::ShufflingErrors::EmptyPacks;
-    use crate::distribution_shuffle::{shuffle, Odds, Pile};
+    use crate::distribution_shuffle::{shuffle, Odds, Pack, Pile};
+    use crate::verify::verify_packs;
 
     prop_compose! {
         fn arb_odds()(odds in 0f64..=1f64) -> Odds{
@@ -158,6 +324,61 @@ mod tests {
         }
     }
 
+    prop_compose! {
+        fn arb_mixed_piles
+            ()
+            (piles in hash_map(any::<String>(), (arb_pile(0, 200), any::<bool>()), 1..20))
+            -> HashMap<String, Pile>{
+            piles
+                .into_iter()
+                .map(|(name, (pile, fixed))| (name, if fixed { Pile { randomness: 0.0, ..pile } } else { pile }))
+                .collect()
+        }
+    }
+
+    // Whether the randomized piles are sure to cover the leftovers and the piles with 0% randomness always fit in the packs.
+    fn spread_alone(piles: &HashMap<String, Pile>, pack_size: usize) -> bool {
+        let cards: usize = piles.values().map(|p| p.cards).sum();
+        let pack_count = cards / pack_size;
+        let randomized: usize = piles
+            .values()
+            .filter(|p| p.randomness != 0.0)
+            .map(|p| p.cards)
+            .sum();
+        let most_fixed: usize = piles
+            .values()
+            .filter(|p| p.randomness == 0.0)
+            .map(|p| p.cards.div_ceil(pack_count.max(1)))
+            .sum();
+        randomized >= cards % pack_size && most_fixed <= pack_size
+    }
+
+    fn pack_counts(packs: &[Pack<&String>], pile_name: &String) -> Vec<usize> {
+        packs
+            .iter()
+            .map(|p| p.card_sources.get(pile_name).copied().unwrap_or(0))
+            .collect()
+    }
+
+    #[test]
+    fn fixing_a_miscount_keeps_other_piles() {
+        let pile = |cards, randomness| Pile { cards, randomness };
+        let piles: HashMap<String, Pile> = HashMap::from([
+            (String::from("Green"), pile(50, 0.0)),
+            (String::from("Blue"), pile(50, 0.0)),
+            (String::from("Multicolored"), pile(100, 0.2)),
+            (String::from("Lands"), pile(20, 0.0)),
+        ]);
+        let shuffled = shuffle(&piles, 15, &mut StdRng::seed_from_u64(7)).unwrap();
+        let mut fixed_piles = piles.clone();
+        fixed_piles.get_mut("Lands").unwrap().cards = 21;
+        let fixed = shuffle(&fixed_piles, 15, &mut StdRng::seed_from_u64(7)).unwrap();
+        for name in ["Green", "Blue"] {
+            let name = String::from(name);
+            assert_eq!(pack_counts(&shuffled, &name), pack_counts(&fixed, &name));
+        }
+    }
+
     proptest! {
         #[test]
         fn shuffled_cards (
@@ -188,14 +409,26 @@ mod tests {
             assert!(card_sources_count.iter().all(|c| {*c > 0}));
 
             // Total cards should equal expected filled packs sum
-            assert_eq!((total_card_count / pack_size) * pack_size, card_sources_count.iter().sum());
+            assert_eq!((total_card_count / pack_size) * pack_size, card_sources_count.iter().sum::<usize>());
 
             // All packs most be requested size
             for pack in shuffled {
-                assert_eq!(pack_size, pack.card_sources.values().sum());
+                assert_eq!(pack_size, pack.card_sources.values().sum::<usize>());
             }
         }
 
+        #[test]
+        fn shuffles_verify(
+            piles in arb_piles(),
+            seed in any::<u64>(),
+        ){
+            let mut rng = StdRng::seed_from_u64(seed);
+            let total_card_count: usize = piles.values().map(|p| p.cards).sum();
+            let pack_size = rng.gen_range(1..=total_card_count.max(1));
+            let shuffled = shuffle(&piles, pack_size, &mut rng).unwrap();
+            prop_assert!(verify_packs(&piles, pack_size, &shuffled).is_empty());
+        }
+
         #[test]
         fn empty_packs(
             piles in arb_piles(),
@@ -205,5 +438,50 @@ mod tests {
             let shuffled = shuffle(&piles, 0, &mut rng);
             assert_eq!(Err(EmptyPacks), shuffled);
         }
+
+        #[test]
+        fn pile_order_independent(
+            piles in hash_map(any::<String>(), arb_pile(0, 200), 0..20),
+            seed in any::<u64>(),
+            pack_size in 1usize..100,
+        ){
+            let mut reordered: Vec<(String, Pile)> = piles.clone().into_iter().collect();
+            reordered.reverse();
+            let reordered: HashMap<String, Pile> = reordered.into_iter().collect();
+
+            let shuffled = shuffle(&piles, pack_size, &mut StdRng::seed_from_u64(seed)).unwrap();
+            let reshuffled = shuffle(&reordered, pack_size, &mut StdRng::seed_from_u64(seed)).unwrap();
+            assert_eq!(shuffled, reshuffled);
+        }
+
+        #[test]
+        fn unrelated_piles_keep_counts(
+            piles in arb_mixed_piles(),
+            seed in any::<u64>(),
+            pack_size in 1usize..100,
+            edited_index in any::<prop::sample::Index>(),
+            edited_randomness in arb_odds(),
+            counted_index in any::<prop::sample::Index>(),
+            counted_cards in 0usize..200,
+        ){
+            let mut names: Vec<&String> = piles.keys().collect();
+            names.sort();
+            let edited_name = edited_index.get(&names).to_owned();
+            let counted_name = counted_index.get(&names).to_owned();
+            let mut edited_piles = piles.clone();
+            edited_piles.get_mut(edited_name).unwrap().randomness = edited_randomness;
+            edited_piles.get_mut(counted_name).unwrap().cards = counted_cards;
+
+            let shuffled = shuffle(&piles, pack_size, &mut StdRng::seed_from_u64(seed)).unwrap();
+            let edited = shuffle(&edited_piles, pack_size, &mut StdRng::seed_from_u64(seed)).unwrap();
+            assert!(edited.iter().all(|p| p.card_sources.values().sum::<usize>() == pack_size));
+
+            // Piles without randomness only ever receive their own cards, spread on their own
+            if shuffled.len() == edited.len() && spread_alone(&piles, pack_size) && spread_alone(&edited_piles, pack_size) {
+                for (name, pile) in piles.iter().filter(|(n, p)| *n != edited_name && *n != counted_name && p.randomness == 0.0) {
+                    assert_eq!(pack_counts(&shuffled, name), pack_counts(&edited, name), "Pile {} with {} cards changed", name, pile.cards);
+                }
+            }
+        }
     }
 }
@@ -0,0 +1,326 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::distribution_shuffle::{Odds, Pile};
+use crate::import::ImportErrors::{
+    EmptyList, InvalidQuantity, MissingNameColumn, UnclosedQuote, UnknownColumn,
+};
+
+pub const SECTION_COLUMN: &str = "section";
+pub const UNGROUPED: &str = "Other";
+const DEFAULT_COLUMNS: [&str; 2] = ["color category", SECTION_COLUMN];
+// Larger numbers are read as the start of a name such as "1996 World Champion" instead of a quantity.
+const MAX_QUANTITY: usize = 100;
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ImportedCard {
+    pub name: String,
+    pub fields: HashMap<String, String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ImportErrors {
+    EmptyList,
+    MissingNameColumn,
+    UnclosedQuote,
+    InvalidQuantity { line: usize },
+    UnknownColumn { column: String },
+}
+
+fn csv_rows(text: &str) -> Result<Vec<Vec<String>>, ImportErrors> {
+    let mut rows: Vec<Vec<String>> = Vec::new();
+    let mut row: Vec<String> = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (quoted, c) {
+            (true, '"') if chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            (true, '"') => quoted = false,
+            (true, _) => field.push(c),
+            (false, '"') => quoted = true,
+            (false, ',') => row.push(std::mem::take(&mut field)),
+            (false, '\r') => {}
+            (false, '\n') => {
+                row.push(std::mem::take(&mut field));
+                rows.push(std::mem::take(&mut row));
+            }
+            (false, _) => field.push(c),
+        }
+    }
+    if quoted {
+        return Err(UnclosedQuote);
+    }
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        rows.push(row);
+    }
+    rows.retain(|r| r.iter().any(|f| !f.trim().is_empty()));
+    Ok(rows)
+}
+
+// Parses a CSV export, such as the one from CubeCobra. Column names are matched case-insensitively
+// and cards marked as maybeboard are skipped.
+pub fn parse_csv(text: &str) -> Result<Vec<ImportedCard>, ImportErrors> {
+    let mut rows = csv_rows(text)?.into_iter();
+    let header: Vec<String> = rows
+        .next()
+        .ok_or(EmptyList)?
+        .iter()
+        .map(|h| h.trim().to_lowercase())
+        .collect();
+    let name_column = header
+        .iter()
+        .position(|h| h == "name")
+        .ok_or(MissingNameColumn)?;
+
+    let cards: Vec<ImportedCard> = rows
+        .map(|row| {
+            let fields: HashMap<String, String> = header
+                .iter()
+                .cloned()
+                .zip(row.iter().map(|f| f.trim().to_owned()))
+                .collect();
+            ImportedCard {
+                name: row.get(name_column).map_or("", |n| n.trim()).to_owned(),
+                fields,
+            }
+        })
+        .filter(|card| !card.name.is_empty())
+        .filter(|card| {
+            !card
+                .fields
+                .get("maybeboard")
+                .is_some_and(|m| m.eq_ignore_ascii_case("true"))
+        })
+        .collect();
+    match cards.is_empty() {
+        true => Err(EmptyList),
+        false => Ok(cards),
+    }
+}
+
+// Only digits with an optional "x" are a quantity, so names such as "7th Edition Card" are kept whole.
+fn quantity(word: &str) -> Option<usize> {
+    let digits = word.strip_suffix(['x', 'X']).unwrap_or(word);
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    digits.parse().ok().filter(|q| *q <= MAX_QUANTITY)
+}
+
+// Parses a plain text list with one "1 Card Name" entry per line. The quantity is optional and may be written as "1x".
+// Comment lines starting with "//" or "#" name the section of the cards below them.
+pub fn parse_text(text: &str) -> Result<Vec<ImportedCard>, ImportErrors> {
+    let mut cards: Vec<ImportedCard> = Vec::new();
+    let mut section = String::new();
+    for (index, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        if let Some(comment) = line.strip_prefix("//").or_else(|| line.strip_prefix('#')) {
+            section = comment.trim().to_owned();
+            continue;
+        }
+
+        let (quantity, name) = match line
+            .split_once(' ')
+            .and_then(|(first, rest)| Some((quantity(first)?, rest)))
+        {
+            // A line of no cards is most likely a typo, so it is reported instead of skipped.
+            Some((0, _)) => return Err(InvalidQuantity { line: index + 1 }),
+            Some((quantity, rest)) => (quantity, rest.trim()),
+            None => (1, line),
+        };
+        let fields = HashMap::from([(SECTION_COLUMN.to_owned(), section.clone())]);
+        for _ in 0..quantity {
+            cards.push(ImportedCard {
+                name: name.to_owned(),
+                fields: fields.clone(),
+            });
+        }
+    }
+    match cards.is_empty() {
+        true => Err(EmptyList),
+        false => Ok(cards),
+    }
+}
+
+// Lists whose first line has a "name" column are read as CSV, everything else as plain text.
+pub fn parse(text: &str) -> Result<Vec<ImportedCard>, ImportErrors> {
+    let is_csv = text
+        .lines()
+        .find(|l| !l.trim().is_empty())
+        .is_some_and(|header| {
+            header
+                .split(',')
+                .any(|column| column.trim().trim_matches('"').eq_ignore_ascii_case("name"))
+        });
+    match is_csv {
+        true => parse_csv(text),
+        false => parse_text(text),
+    }
+}
+
+// Groups the card names by the value of a column, cards without a value end up in the "Other" group.
+// Without a column the CubeCobra color category is used for CSV lists and the sections for plain text lists.
+pub fn group(
+    cards: &[ImportedCard],
+    group_by: Option<&str>,
+) -> Result<HashMap<String, Vec<String>>, ImportErrors> {
+    let column = match group_by {
+        Some(column) => column.trim().to_lowercase(),
+        None => DEFAULT_COLUMNS
+            .iter()
+            .find(|column| cards.iter().any(|c| c.fields.contains_key(**column)))
+            .unwrap_or(&SECTION_COLUMN)
+            .to_string(),
+    };
+    if !cards.iter().any(|c| c.fields.contains_key(&column)) {
+        return Err(UnknownColumn { column });
+    }
+
+    let mut groups: HashMap<String, Vec<String>> = HashMap::new();
+    for card in cards {
+        let group = card
+            .fields
+            .get(&column)
+            .filter(|v| !v.is_empty())
+            .map_or(UNGROUPED, |v| v.as_str());
+        groups
+            .entry(group.to_owned())
+            .or_default()
+            .push(card.name.clone());
+    }
+    Ok(groups)
+}
+
+pub fn piles(groups: &HashMap<String, Vec<String>>, randomness: Odds) -> HashMap<String, Pile> {
+    groups
+        .iter()
+        .map(|(name, cards)| {
+            (
+                name.clone(),
+                Pile {
+                    cards: cards.len(),
+                    randomness,
+                },
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use proptest::prelude::*;
+
+    use crate::import::ImportErrors::{EmptyList, InvalidQuantity, UnclosedQuote, UnknownColumn};
+    use crate::import::{group, parse, parse_csv, parse_text, piles};
+
+    const CUBE_COBRA: &str = "\
+name,CMC,Type,Color,Set,Collector Number,Rarity,Color Category,status,Finish,maybeboard,image URL,image Back URL,tags,Notes,MTGO ID
+\"Swords to Plowshares\",1,\"Instant\",W,sta,10,uncommon,White,Owned,Non-foil,false,,,,,
+\"Counterspell\",2,\"Instant\",U,sta,15,uncommon,Blue,Owned,Non-foil,false,,,\"Control\",\"Classic, \"\"must\"\" include\",
+\"Brainstorm\",1,\"Instant\",U,sta,13,common,Blue,Owned,Non-foil,false,,,,,
+\"Black Lotus\",0,\"Artifact\",C,lea,232,rare,Colorless,Not Owned,Non-foil,true,,,,,
+";
+
+    #[test]
+    fn cube_cobra_csv() {
+        let cards = parse(CUBE_COBRA).unwrap();
+        assert_eq!(3, cards.len());
+        assert_eq!("Classic, \"must\" include", cards[1].fields["notes"]);
+
+        let groups = group(&cards, Some("Color Category")).unwrap();
+        assert_eq!(
+            HashMap::from([
+                ("White".to_owned(), vec!["Swords to Plowshares".to_owned()]),
+                (
+                    "Blue".to_owned(),
+                    vec!["Counterspell".to_owned(), "Brainstorm".to_owned()]
+                ),
+            ]),
+            groups
+        );
+
+        assert_eq!(groups, group(&cards, None).unwrap());
+
+        let tags = group(&cards, Some("tags")).unwrap();
+        assert_eq!(2, tags["Other"].len());
+        assert_eq!(1, tags["Control"].len());
+
+        let pile_counts = piles(&groups, 0.1);
+        assert_eq!(2, pile_counts["Blue"].cards);
+        assert_eq!(
+            Err(UnknownColumn {
+                column: "power".to_owned()
+            }),
+            group(&cards, Some("Power"))
+        );
+    }
+
+    #[test]
+    fn plain_text() {
+        let cards =
+            parse("// Blue\n1 Counterspell\n2x Brainstorm\n\n# Lands\nFlooded Strand\n").unwrap();
+        assert_eq!(4, cards.len());
+        let groups = group(&cards, None).unwrap();
+        assert_eq!(3, groups["Blue"].len());
+        assert_eq!(vec!["Flooded Strand".to_owned()], groups["Lands"]);
+    }
+
+    #[test]
+    fn invalid_lists() {
+        assert_eq!(Err(EmptyList), parse(""));
+        assert_eq!(Err(EmptyList), parse_csv("name,color\n"));
+        assert_eq!(Err(UnclosedQuote), parse_csv("name\n\"Brainstorm\n"));
+        assert_eq!(
+            Err(InvalidQuantity { line: 2 }),
+            parse_text("1 Brainstorm\n0x Counterspell")
+        );
+    }
+
+    #[test]
+    fn names_with_numbers() {
+        let cards = parse_text(
+            "7th Edition Card\n1y Counterspell\n1996 World Champion\n99999999999999999999x Island",
+        )
+        .unwrap();
+        let names: Vec<&str> = cards.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(
+            vec![
+                "7th Edition Card",
+                "1y Counterspell",
+                "1996 World Champion",
+                "99999999999999999999x Island"
+            ],
+            names
+        );
+        assert_eq!(100, parse_text("100x Island").unwrap().len());
+    }
+
+    proptest! {
+        #[test]
+        fn text_quantities(quantities in proptest::collection::vec(1usize..5, 1..20)) {
+            let text: String = quantities
+                .iter()
+                .enumerate()
+                .map(|(index, quantity)| format!("{} Card {}\n", quantity, index))
+                .collect();
+            let cards = parse(&text).unwrap();
+            assert_eq!(quantities.iter().sum::<usize>(), cards.len());
+        }
+
+        #[test]
+        fn parse_arbitrary(text in any::<String>()) {
+            let _ = parse(&text);
+        }
+    }
+}
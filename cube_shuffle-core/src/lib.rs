@@ -1 +1,14 @@
+pub mod analysis;
+pub mod balance;
+pub mod booster;
+pub mod commit_reveal;
+pub mod config;
+pub mod dice;
 pub mod distribution_shuffle;
+pub mod import;
+pub mod print_sheet;
+pub mod report;
+pub mod scryfall;
+pub mod seating;
+pub mod seed;
+pub mod verify;
@@ -0,0 +1,137 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use rand::{Rng, RngCore};
+use serde::{Deserialize, Serialize};
+
+use crate::distribution_shuffle::{layout, Pack, Pile, ShufflingErrors};
+use crate::seed::{seeded_rng, Stream};
+
+// A run of consecutive cards counted from the top of a pile, wrapping around to the top after the bottom card.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Cut<P> {
+    pub pile: P,
+    pub start: usize,
+    pub cards: usize,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SheetPack<P>
+where
+    P: Hash + Eq + Serialize,
+{
+    pub pack: Pack<P>,
+    pub cuts: Vec<Cut<P>>,
+}
+
+// Each pile is treated as a print sheet which packs are cut from one after another, starting at a random position.
+// Pack counts come from the same layout as the distribution shuffle, so piles with 0% randomness are spread on their own
+// and the others fill the rest, but the randomness is not used to move single cards between packs afterwards.
+pub fn print_sheet<'a, P>(
+    piles: &'a HashMap<P, Pile>,
+    pack_size: usize,
+    random: &mut impl RngCore,
+) -> Result<Vec<SheetPack<&'a P>>, ShufflingErrors>
+where
+    P: Eq + Hash + Ord + Serialize + Stream,
+{
+    let seed = random.next_u64();
+    let (pack_count, pile_layouts) = layout(piles, pack_size, seed)?;
+
+    let mut packs: Vec<SheetPack<&P>> = vec![
+        SheetPack {
+            pack: Pack {
+                card_sources: HashMap::new()
+            },
+            cuts: Vec::new()
+        };
+        pack_count
+    ];
+    for (pile_name, pile, pack_cards) in pile_layouts {
+        if pile.cards == 0 {
+            continue;
+        }
+        let mut position: usize = seeded_rng(seed, &("sheet", pile_name)).gen_range(0..pile.cards);
+        for (pack, cards) in packs.iter_mut().zip(pack_cards) {
+            if cards == 0 {
+                continue;
+            }
+            pack.pack.card_sources.insert(pile_name, cards);
+            pack.cuts.push(Cut {
+                pile: pile_name,
+                start: position,
+                cards,
+            });
+            position = (position + cards) % pile.cards;
+        }
+    }
+
+    Ok(packs)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{HashMap, HashSet};
+
+    use proptest::collection::hash_map;
+    use proptest::prelude::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use crate::distribution_shuffle::{shuffle, Pile};
+    use crate::print_sheet::print_sheet;
+
+    prop_compose! {
+        fn arb_piles
+            ()
+            (piles in hash_map(any::<String>(), 0usize..200, 0..20))
+            -> HashMap<String, Pile>{
+            piles
+                .into_iter()
+                .map(|(name, cards)| (name, Pile { cards, randomness: 0.0 }))
+                .collect()
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn consecutive_cuts(
+            piles in arb_piles(),
+            pack_size in 1usize..30,
+            seed in any::<u64>(),
+        ){
+            let packs = print_sheet(&piles, pack_size, &mut StdRng::seed_from_u64(seed)).unwrap();
+
+            for pack in packs.iter() {
+                assert_eq!(pack_size, pack.pack.card_sources.values().sum::<usize>());
+                for cut in pack.cuts.iter() {
+                    assert_eq!(Some(&cut.cards), pack.pack.card_sources.get(cut.pile));
+                }
+            }
+
+            for (name, pile) in piles.iter() {
+                let cuts: Vec<_> = packs.iter().flat_map(|p| p.cuts.iter()).filter(|c| c.pile == name).collect();
+                for (cut, next) in cuts.iter().zip(cuts.iter().skip(1)) {
+                    assert_eq!((cut.start + cut.cards) % pile.cards, next.start);
+                }
+                let positions: HashSet<usize> = cuts
+                    .iter()
+                    .flat_map(|c| (c.start..(c.start + c.cards)).map(|p| p % pile.cards))
+                    .collect();
+                assert_eq!(cuts.iter().map(|c| c.cards).sum::<usize>(), positions.len());
+            }
+        }
+
+        #[test]
+        fn matches_even_distribution(
+            piles in arb_piles(),
+            pack_size in 1usize..30,
+            seed in any::<u64>(),
+        ){
+            let sheet_packs = print_sheet(&piles, pack_size, &mut StdRng::seed_from_u64(seed)).unwrap();
+            let shuffled_packs = shuffle(&piles, pack_size, &mut StdRng::seed_from_u64(seed)).unwrap();
+            let sheet_packs: Vec<_> = sheet_packs.into_iter().map(|p| p.pack).collect();
+            assert_eq!(shuffled_packs, sheet_packs);
+        }
+    }
+}
@@ -0,0 +1,189 @@
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::hash::Hash;
+
+use serde::Serialize;
+
+use crate::config::Config;
+use crate::distribution_shuffle::Pack;
+
+const STYLE: &str = "\
+body { font-family: sans-serif; margin: 2em; }
+h1 { margin-bottom: 0; }
+table { border-collapse: collapse; }
+th, td { border: 1px solid #999; padding: 0.2em 0.6em; text-align: left; }
+td.count { text-align: right; }
+.packs { display: flex; flex-wrap: wrap; gap: 1em; }
+.pack { break-inside: avoid; page-break-inside: avoid; }
+@media print { body { margin: 0; } }
+";
+
+fn escape(text: &str) -> String {
+    text.chars()
+        .map(|c| match c {
+            '&' => String::from("&amp;"),
+            '<' => String::from("&lt;"),
+            '>' => String::from("&gt;"),
+            '"' => String::from("&quot;"),
+            '\'' => String::from("&#39;"),
+            _ => c.to_string(),
+        })
+        .collect()
+}
+
+fn count_table<'a>(rows: impl Iterator<Item = (&'a str, String)>) -> String {
+    let mut table = String::from("<table><tbody>");
+    for (name, count) in rows {
+        write!(
+            table,
+            "<tr><th>{}</th><td class=\"count\">{}</td></tr>",
+            escape(name),
+            count
+        )
+        .unwrap();
+    }
+    table.push_str("</tbody></table>");
+    table
+}
+
+// Renders a self-contained HTML page, without any external resources, for printing the packs at the table.
+pub fn html_report<P>(config: &Config, packs: &[Pack<P>]) -> String
+where
+    P: AsRef<str> + Hash + Eq + Serialize,
+{
+    let mut pile_names: Vec<&String> = config.piles.keys().collect();
+    pile_names.sort();
+
+    let mut used: HashMap<&str, usize> = HashMap::new();
+    for pack in packs {
+        for (pile_name, cards) in pack.card_sources.iter() {
+            *used.entry(pile_name.as_ref()).or_insert(0) += cards;
+        }
+    }
+
+    let mut html =
+        String::from("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+    write!(
+        html,
+        "<title>CubeShuffle packs</title>\n<style>\n{}</style>\n</head>\n<body>\n",
+        STYLE
+    )
+    .unwrap();
+    html.push_str("<h1>CubeShuffle packs</h1>\n");
+    writeln!(
+        html,
+        "<p>Seed <strong>{}</strong>, {} packs of {} cards, CubeShuffle {}</p>",
+        escape(&config.seed),
+        packs.len(),
+        config.pack_size,
+        escape(&config.version)
+    )
+    .unwrap();
+
+    html.push_str("<h2>Piles</h2>\n<table><thead><tr><th>Pile</th><th>Cards</th><th>Randomness</th></tr></thead><tbody>");
+    for pile_name in pile_names.iter() {
+        let pile = config.piles[*pile_name];
+        write!(
+            html,
+            "<tr><th>{}</th><td class=\"count\">{}</td><td class=\"count\">{}%</td></tr>",
+            escape(pile_name),
+            pile.cards,
+            (pile.randomness * 100.0).round()
+        )
+        .unwrap();
+    }
+    html.push_str("</tbody></table>\n");
+
+    html.push_str("<h2>Packs</h2>\n<div class=\"packs\">\n");
+    for (index, pack) in packs.iter().enumerate() {
+        let mut sources: Vec<(&str, usize)> = pack
+            .card_sources
+            .iter()
+            .map(|(pile_name, cards)| (pile_name.as_ref(), *cards))
+            .collect();
+        sources.sort();
+        writeln!(
+            html,
+            "<div class=\"pack\"><h3>Pack {}</h3>{}</div>",
+            index + 1,
+            count_table(
+                sources
+                    .into_iter()
+                    .map(|(name, cards)| (name, cards.to_string()))
+            )
+        )
+        .unwrap();
+    }
+    html.push_str("</div>\n");
+
+    html.push_str("<h2>Leftovers</h2>\n");
+    let leftovers: Vec<(&str, String)> = pile_names
+        .iter()
+        .map(|pile_name| {
+            // Hand edited packs may deal more cards than a pile has, which leaves none over.
+            let left = config.piles[*pile_name]
+                .cards
+                .saturating_sub(*used.get(pile_name.as_str()).unwrap_or(&0));
+            (pile_name.as_str(), left)
+        })
+        .filter(|(_, left)| *left > 0)
+        .map(|(pile_name, left)| (pile_name, left.to_string()))
+        .collect();
+    if leftovers.is_empty() {
+        html.push_str("<p>No cards are left over.</p>\n");
+    } else {
+        html.push_str(&count_table(leftovers.into_iter()));
+        html.push('\n');
+    }
+
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::config::Config;
+    use crate::distribution_shuffle::Pile;
+    use crate::report::html_report;
+
+    #[test]
+    fn report_contents() {
+        let config = Config::new(
+            String::from("<Friday>"),
+            2,
+            HashMap::from([
+                (
+                    String::from("Green"),
+                    Pile {
+                        cards: 3,
+                        randomness: 0.07,
+                    },
+                ),
+                (
+                    String::from("Blue & Black"),
+                    Pile {
+                        cards: 2,
+                        randomness: 0.0,
+                    },
+                ),
+            ]),
+        );
+        let packs = config.shuffle().unwrap();
+        let report = html_report(&config, &packs);
+
+        assert_eq!(2, report.matches("<h3>Pack ").count());
+        assert!(report.contains("&lt;Friday&gt;"));
+        assert!(report.contains("Blue &amp; Black"));
+        assert!(report.contains("<td class=\"count\">7%</td>"));
+        assert!(report.contains("<h2>Leftovers</h2>\n<table>"));
+        assert!(!report.contains("http"));
+
+        let mut overdrawn = packs.clone();
+        for pile_name in config.piles.keys() {
+            *overdrawn[0].card_sources.entry(pile_name).or_insert(0) += 5;
+        }
+        assert!(html_report(&config, &overdrawn).contains("<p>No cards are left over.</p>"));
+    }
+}
@@ -0,0 +1,205 @@
+use std::collections::HashMap;
+use std::io::Read;
+
+use parse_display::{Display, FromStr};
+use serde::{Deserialize, Serialize};
+
+use crate::import::ImportedCard;
+use crate::scryfall::ScryfallErrors::InvalidBulkData;
+
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    Hash,
+    PartialOrd,
+    Ord,
+    Display,
+    FromStr,
+    Serialize,
+    Deserialize,
+)]
+pub enum Category {
+    White,
+    Blue,
+    Black,
+    Red,
+    Green,
+    Multicolored,
+    Colorless,
+    Lands,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ScryfallErrors {
+    InvalidBulkData { message: String },
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Classification {
+    pub groups: HashMap<String, Vec<String>>,
+    pub unknown: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct ScryfallFace {
+    name: String,
+    colors: Option<Vec<String>>,
+    type_line: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ScryfallCard {
+    name: String,
+    colors: Option<Vec<String>>,
+    type_line: Option<String>,
+    #[serde(default)]
+    card_faces: Vec<ScryfallFace>,
+}
+
+pub struct CardDatabase {
+    categories: HashMap<String, Category>,
+}
+
+fn categorize(colors: &[String], type_line: &str) -> Category {
+    if type_line.split_whitespace().any(|t| t == "Land") {
+        return Category::Lands;
+    }
+    match colors {
+        [] => Category::Colorless,
+        [color] => match color.as_str() {
+            "W" => Category::White,
+            "U" => Category::Blue,
+            "B" => Category::Black,
+            "R" => Category::Red,
+            "G" => Category::Green,
+            _ => Category::Colorless,
+        },
+        _ => Category::Multicolored,
+    }
+}
+
+fn normalize(name: &str) -> String {
+    name.trim().to_lowercase()
+}
+
+impl ScryfallCard {
+    // Cards with several faces are categorized by their front face, like they are sorted in most cubes.
+    fn category(&self) -> Category {
+        let front = self.card_faces.first();
+        let colors = front
+            .and_then(|f| f.colors.as_ref())
+            .or(self.colors.as_ref())
+            .map_or(&[][..], Vec::as_slice);
+        let type_line = front
+            .and_then(|f| f.type_line.as_deref())
+            .or(self.type_line.as_deref())
+            .unwrap_or("");
+        categorize(colors, type_line)
+    }
+}
+
+impl CardDatabase {
+    // Reads a Scryfall bulk data file, such as "Oracle Cards", which is a JSON array of card objects.
+    pub fn load(reader: impl Read) -> Result<Self, ScryfallErrors> {
+        let cards: Vec<ScryfallCard> =
+            serde_json::from_reader(reader).map_err(|e| InvalidBulkData {
+                message: e.to_string(),
+            })?;
+        let mut categories: HashMap<String, Category> = HashMap::new();
+        for card in cards {
+            let category = card.category();
+            for face in card.card_faces.iter() {
+                categories.entry(normalize(&face.name)).or_insert(category);
+            }
+            categories.entry(normalize(&card.name)).or_insert(category);
+        }
+        Ok(Self { categories })
+    }
+
+    pub fn category(&self, name: &str) -> Option<Category> {
+        self.categories.get(&normalize(name)).copied()
+    }
+
+    pub fn classify(&self, cards: &[ImportedCard]) -> Classification {
+        let mut groups: HashMap<String, Vec<String>> = HashMap::new();
+        let mut unknown: Vec<String> = Vec::new();
+        for card in cards {
+            match self.category(&card.name) {
+                Some(category) => groups
+                    .entry(category.to_string())
+                    .or_default()
+                    .push(card.name.clone()),
+                None => unknown.push(card.name.clone()),
+            }
+        }
+        Classification { groups, unknown }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::import::parse;
+    use crate::scryfall::{CardDatabase, Category};
+
+    const BULK: &str = r#"[
+        {"object": "card", "name": "Swords to Plowshares", "colors": ["W"], "type_line": "Instant"},
+        {"object": "card", "name": "Counterspell", "colors": ["U"], "type_line": "Instant"},
+        {"object": "card", "name": "Lightning Helix", "colors": ["R", "W"], "type_line": "Instant"},
+        {"object": "card", "name": "Sol Ring", "colors": [], "type_line": "Artifact"},
+        {"object": "card", "name": "Dryad Arbor", "colors": ["G"], "type_line": "Land Creature — Forest Dryad"},
+        {"object": "card", "name": "Delver of Secrets // Insectile Aberration", "type_line": "Creature — Human Wizard // Creature — Human Insect",
+            "card_faces": [
+                {"name": "Delver of Secrets", "colors": ["U"], "type_line": "Creature — Human Wizard"},
+                {"name": "Insectile Aberration", "colors": ["U"], "type_line": "Creature — Human Insect"}
+            ]},
+        {"object": "card", "name": "Fable of the Mirror-Breaker // Reflection of Kiki-Jiki", "colors": ["R"], "type_line": "Enchantment — Saga // Enchantment Creature — Goblin Shaman",
+            "card_faces": [
+                {"name": "Fable of the Mirror-Breaker", "type_line": "Enchantment — Saga"},
+                {"name": "Reflection of Kiki-Jiki", "type_line": "Enchantment Creature — Goblin Shaman"}
+            ]}
+    ]"#;
+
+    #[test]
+    fn categories() {
+        let database = CardDatabase::load(BULK.as_bytes()).unwrap();
+        assert_eq!(
+            Some(Category::White),
+            database.category("Swords to Plowshares")
+        );
+        assert_eq!(Some(Category::Blue), database.category("counterspell"));
+        assert_eq!(
+            Some(Category::Multicolored),
+            database.category("Lightning Helix")
+        );
+        assert_eq!(Some(Category::Colorless), database.category("Sol Ring"));
+        assert_eq!(Some(Category::Lands), database.category("Dryad Arbor"));
+        assert_eq!(Some(Category::Blue), database.category("Delver of Secrets"));
+        assert_eq!(
+            Some(Category::Blue),
+            database.category("Delver of Secrets // Insectile Aberration")
+        );
+        assert_eq!(
+            Some(Category::Red),
+            database.category("Fable of the Mirror-Breaker")
+        );
+        assert_eq!(None, database.category("Black Lotus"));
+    }
+
+    #[test]
+    fn classify() {
+        let database = CardDatabase::load(BULK.as_bytes()).unwrap();
+        let cards = parse("2 Counterspell\n1 Sol Ring\n1 Black Lotus\n").unwrap();
+        let classification = database.classify(&cards);
+        assert_eq!(2, classification.groups["Blue"].len());
+        assert_eq!(1, classification.groups["Colorless"].len());
+        assert_eq!(vec!["Black Lotus".to_owned()], classification.unknown);
+    }
+
+    #[test]
+    fn invalid_bulk_data() {
+        assert!(CardDatabase::load("{}".as_bytes()).is_err());
+    }
+}
@@ -0,0 +1,117 @@
+use rand::prelude::SliceRandom;
+use serde::{Deserialize, Serialize};
+
+use crate::seating::SeatingErrors::{EmptyTeams, UnevenTeams};
+use crate::seed::seeded_rng;
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Seating<P>
+where
+    P: Serialize,
+{
+    pub seats: Vec<P>,
+    pub teams: Vec<Vec<P>>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub enum SeatingErrors {
+    EmptyTeams,
+    UnevenTeams { players: usize, teams: usize },
+}
+
+// Teams alternate around the table, as is customary for team drafts.
+pub fn seat<P>(players: &[P], teams: Option<usize>, seed: u64) -> Result<Seating<&P>, SeatingErrors>
+where
+    P: Serialize,
+{
+    let team_count = match teams {
+        None => 0,
+        Some(0) => return Err(EmptyTeams),
+        Some(t) if !players.len().is_multiple_of(t) => {
+            return Err(UnevenTeams {
+                players: players.len(),
+                teams: t,
+            })
+        }
+        Some(t) => t,
+    };
+
+    let mut random = seeded_rng(seed, "seating");
+    let mut seats: Vec<&P> = players.iter().collect();
+    seats.shuffle(&mut random);
+
+    let mut seated_teams: Vec<Vec<&P>> = vec![Vec::new(); team_count];
+    if team_count > 0 {
+        for (index, player) in seats.iter().enumerate() {
+            seated_teams[index % team_count].push(*player);
+        }
+    }
+
+    Ok(Seating {
+        seats,
+        teams: seated_teams,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::collection::vec;
+    use proptest::prelude::*;
+
+    use crate::seating::SeatingErrors::{EmptyTeams, UnevenTeams};
+    use crate::seating::{seat, Seating};
+
+    proptest! {
+        #[test]
+        fn seats_all_players(
+            players in vec(any::<String>(), 0..50),
+            seed in any::<u64>(),
+        ){
+            let seating = seat(&players, None, seed).unwrap();
+            let mut seated: Vec<&String> = seating.seats.clone();
+            let mut expected: Vec<&String> = players.iter().collect();
+            seated.sort();
+            expected.sort();
+            assert_eq!(expected, seated);
+            assert!(seating.teams.is_empty());
+        }
+
+        #[test]
+        fn seating_deterministic(
+            players in vec(any::<String>(), 0..50),
+            seed in any::<u64>(),
+        ){
+            assert_eq!(seat(&players, None, seed), seat(&players, None, seed));
+        }
+
+        #[test]
+        fn alternating_teams(
+            team_size in 1usize..10,
+            teams in 1usize..5,
+            seed in any::<u64>(),
+        ){
+            let players: Vec<usize> = (0..(team_size * teams)).collect();
+            let Seating { seats, teams: seated_teams } = seat(&players, Some(teams), seed).unwrap();
+            assert_eq!(teams, seated_teams.len());
+            for (index, player) in seats.iter().enumerate() {
+                assert!(seated_teams[index % teams].contains(player));
+            }
+            for team in seated_teams {
+                assert_eq!(team_size, team.len());
+            }
+        }
+    }
+
+    #[test]
+    fn invalid_teams() {
+        let players = vec!["A", "B", "C"];
+        assert_eq!(Err(EmptyTeams), seat(&players, Some(0), 0));
+        assert_eq!(
+            Err(UnevenTeams {
+                players: 3,
+                teams: 2
+            }),
+            seat(&players, Some(2), 0)
+        );
+    }
+}
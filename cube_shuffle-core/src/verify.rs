@@ -0,0 +1,281 @@
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use serde::{Deserialize, Serialize};
+
+use crate::distribution_shuffle::{Pack, Pile};
+
+// Packs are indexed from 0 in the order given.
+// Cards are added up in u128, so hand edited counts can not overflow.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub enum Violation<P> {
+    WrongPackSize {
+        pack: usize,
+        cards: u128,
+        pack_size: usize,
+    },
+    OverAllocatedPile {
+        pile: P,
+        cards: usize,
+        dealt: u128,
+    },
+    // More cards of the pile are left over than the packs leave in total.
+    UnderAllocatedPile {
+        pile: P,
+        cards: usize,
+        dealt: u128,
+    },
+    // The piles have more cards than can be shuffled, so they can not have been packs of a shuffle.
+    CardOverflow {
+        cards: u128,
+        max_cards: u128,
+    },
+    UnknownPile {
+        pack: usize,
+        pile: P,
+    },
+    PackCountMismatch {
+        expected: usize,
+        found: usize,
+    },
+}
+
+// Checks the invariants every shuffle result must hold, so hand edited or imported packs can be checked as well.
+pub fn verify_packs<'a, P, Q>(
+    piles: &'a HashMap<P, Pile>,
+    pack_size: usize,
+    packs: &'a [Pack<Q>],
+) -> Vec<Violation<&'a P>>
+where
+    P: Eq + Hash + Ord,
+    Q: Borrow<P> + Eq + Hash + Ord + Serialize,
+{
+    let mut violations = Vec::new();
+
+    let total_cards: u128 = piles.values().map(|p| p.cards as u128).sum();
+    match usize::try_from(total_cards) {
+        Ok(total_cards) => {
+            let expected = total_cards.checked_div(pack_size).unwrap_or(0);
+            if expected != packs.len() {
+                violations.push(Violation::PackCountMismatch {
+                    expected,
+                    found: packs.len(),
+                });
+            }
+        }
+        Err(_) => violations.push(Violation::CardOverflow {
+            cards: total_cards,
+            max_cards: usize::MAX as u128,
+        }),
+    }
+
+    let mut dealt: HashMap<&P, u128> = HashMap::new();
+    for (index, pack) in packs.iter().enumerate() {
+        let cards: u128 = pack.card_sources.values().map(|c| *c as u128).sum();
+        if cards != pack_size as u128 {
+            violations.push(Violation::WrongPackSize {
+                pack: index,
+                cards,
+                pack_size,
+            });
+        }
+        let mut card_sources: Vec<(&Q, &usize)> = pack.card_sources.iter().collect();
+        card_sources.sort();
+        for (pile_name, cards) in card_sources {
+            let pile_name: &P = pile_name.borrow();
+            if !piles.contains_key(pile_name) {
+                violations.push(Violation::UnknownPile {
+                    pack: index,
+                    pile: pile_name,
+                });
+            }
+            *dealt.entry(pile_name).or_insert(0) += *cards as u128;
+        }
+    }
+
+    // Every pile is checked, including those no pack has cards from.
+    let leftovers = total_cards.saturating_sub(packs.len() as u128 * pack_size as u128);
+    let mut pile_names: Vec<&P> = piles.keys().collect();
+    pile_names.sort();
+    for pile_name in pile_names {
+        let cards = piles[pile_name].cards;
+        let dealt = dealt.get(pile_name).copied().unwrap_or(0);
+        if dealt > cards as u128 {
+            violations.push(Violation::OverAllocatedPile {
+                pile: pile_name,
+                cards,
+                dealt,
+            });
+        } else if cards as u128 - dealt > leftovers {
+            violations.push(Violation::UnderAllocatedPile {
+                pile: pile_name,
+                cards,
+                dealt,
+            });
+        }
+    }
+
+    violations
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::distribution_shuffle::{Pack, Pile};
+    use crate::verify::{verify_packs, Violation};
+
+    #[test]
+    fn violations() {
+        let piles = HashMap::from([
+            (
+                "Red",
+                Pile {
+                    cards: 3,
+                    randomness: 0.0,
+                },
+            ),
+            (
+                "Blue",
+                Pile {
+                    cards: 3,
+                    randomness: 0.0,
+                },
+            ),
+        ]);
+        let packs = vec![
+            Pack {
+                card_sources: HashMap::from([("Red", 2), ("Blue", 1)]),
+            },
+            Pack {
+                card_sources: HashMap::from([("Red", 2), ("Green", 1)]),
+            },
+            Pack {
+                card_sources: HashMap::from([("Blue", 2)]),
+            },
+        ];
+        assert_eq!(
+            vec![
+                Violation::PackCountMismatch {
+                    expected: 2,
+                    found: 3
+                },
+                Violation::UnknownPile {
+                    pack: 1,
+                    pile: &"Green"
+                },
+                Violation::WrongPackSize {
+                    pack: 2,
+                    cards: 2,
+                    pack_size: 3
+                },
+                Violation::OverAllocatedPile {
+                    pile: &"Red",
+                    cards: 3,
+                    dealt: 4
+                },
+            ],
+            verify_packs(&piles, 3, &packs)
+        );
+    }
+
+    #[test]
+    fn huge_counts() {
+        let piles = HashMap::from([
+            (
+                "Red",
+                Pile {
+                    cards: usize::MAX,
+                    randomness: 0.0,
+                },
+            ),
+            (
+                "Blue",
+                Pile {
+                    cards: 1,
+                    randomness: 0.0,
+                },
+            ),
+        ]);
+        let packs = vec![Pack {
+            card_sources: HashMap::from([("Red", usize::MAX), ("Blue", 2)]),
+        }];
+        let cards = usize::MAX as u128;
+        assert_eq!(
+            vec![
+                Violation::CardOverflow {
+                    cards: cards + 1,
+                    max_cards: cards
+                },
+                Violation::WrongPackSize {
+                    pack: 0,
+                    cards: cards + 2,
+                    pack_size: 2
+                },
+                Violation::OverAllocatedPile {
+                    pile: &"Blue",
+                    cards: 1,
+                    dealt: 2
+                },
+            ],
+            verify_packs(&piles, 2, &packs)
+        );
+    }
+
+    #[test]
+    fn unused_pile() {
+        let piles = HashMap::from([
+            (
+                "Red",
+                Pile {
+                    cards: 4,
+                    randomness: 0.0,
+                },
+            ),
+            (
+                "Blue",
+                Pile {
+                    cards: 2,
+                    randomness: 0.0,
+                },
+            ),
+        ]);
+        // A misspelled pile fills the packs, so only the totals show that Blue was never dealt.
+        let packs = vec![
+            Pack {
+                card_sources: HashMap::from([("Red", 2), ("Blue", 0), ("Bleu", 1)]),
+            },
+            Pack {
+                card_sources: HashMap::from([("Red", 2), ("Bleu", 1)]),
+            },
+        ];
+        assert_eq!(
+            vec![
+                Violation::UnknownPile {
+                    pack: 0,
+                    pile: &"Bleu"
+                },
+                Violation::UnknownPile {
+                    pack: 1,
+                    pile: &"Bleu"
+                },
+                Violation::UnderAllocatedPile {
+                    pile: &"Blue",
+                    cards: 2,
+                    dealt: 0
+                },
+            ],
+            verify_packs(&piles, 3, &packs)
+        );
+        let packs = vec![
+            Pack {
+                card_sources: HashMap::from([("Red", 2), ("Blue", 1)]),
+            },
+            Pack {
+                card_sources: HashMap::from([("Red", 2), ("Blue", 1)]),
+            },
+        ];
+        assert!(verify_packs(&piles, 3, &packs).is_empty());
+    }
+}
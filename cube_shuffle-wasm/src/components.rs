@@ -1,8 +1,16 @@
 mod add_pile;
 pub mod app;
+mod config_file;
+mod dealer_mode;
+mod dice_seed;
+mod import_piles;
 mod integer_input;
 mod pack_card;
 mod pack_list;
 mod pile_card;
 mod pile_list;
+mod presets;
+mod seating_panel;
+mod shortcuts;
+mod summary_panel;
 mod text_input;
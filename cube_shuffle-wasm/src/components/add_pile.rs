@@ -64,7 +64,7 @@ impl Component for AddPile {
             Msg::UpdateRandomness(randomness) => {
                 self.randomness = match randomness {
                     None => 0,
-                    Some(r) => r.max(0).min(100),
+                    Some(r) => r.clamp(0, 100),
                 };
                 false
             }
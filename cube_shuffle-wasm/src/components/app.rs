@@ -1,57 +1,278 @@
 use std::collections::HashMap;
-use std::io::Cursor;
 
-use byteorder::{BigEndian, ReadBytesExt};
+use indexmap::IndexMap;
 use rand::prelude::StdRng;
 use rand::{RngCore, SeedableRng};
-use sha3::{Digest, Sha3_256};
+use wasm_bindgen::JsValue;
 use yew::prelude::*;
 
+use cube_shuffle_core::config::{Config, VERSION};
 use cube_shuffle_core::distribution_shuffle::{Pack, Pile, ShufflingErrors};
+use cube_shuffle_core::report::html_report;
+use cube_shuffle_core::seed::parse_seed;
 
 use crate::components::add_pile::AddPile;
+use crate::components::config_file::ConfigFile;
+use crate::components::dealer_mode::DealerMode;
+use crate::components::dice_seed::DiceSeed;
+use crate::components::import_piles::ImportPiles;
 use crate::components::integer_input::IntegerInput;
 use crate::components::pack_list::PackList;
 use crate::components::pile_list::PileList;
+use crate::components::presets::Presets;
+use crate::components::seating_panel::SeatingPanel;
+use crate::components::shortcuts::Shortcuts;
+use crate::components::summary_panel::SummaryPanel;
 use crate::components::text_input::TextInput;
+use crate::dealing::Order;
+use crate::history::History;
+use crate::presets::Template;
+use crate::share::{self, Shared};
+use crate::storage;
 
 #[derive(Clone, PartialEq)]
 pub enum Msg {
-    AddPile { name: String, pile: Pile },
+    AddPile {
+        name: String,
+        pile: Pile,
+    },
+    EditPile {
+        old: String,
+        name: String,
+        pile: Pile,
+    },
+    ImportPiles(HashMap<String, Pile>),
+    LoadConfig(Config),
+    UsePreset(Template),
     DelPile(String),
     UpdateSeed(String),
     UpdatePackSize(Option<i128>),
     Pile,
     Shuffle,
+    Check(usize),
+    Done(usize),
+    Deal(Option<Order>),
     Error(Option<String>),
+    Undo,
+    Redo,
+    Reset,
 }
 
-#[derive(Clone, PartialEq, Eq)]
+#[derive(Clone, Copy, PartialEq, Eq)]
 pub enum State {
     Piling,
-    Shuffled { packs: Vec<Pack<String>> },
+    Shuffled,
+}
+
+// Everything undo and redo bring back.
+#[derive(Clone, PartialEq)]
+struct Snapshot {
+    piles: IndexMap<String, Pile>,
+    state: State,
+    seed: String,
+    pack_size: usize,
+    packs: Option<Vec<Pack<String>>>,
+    done: Option<Vec<bool>>,
 }
 
 #[derive(Clone, PartialEq)]
 pub struct App {
-    piles: HashMap<String, Pile>,
+    piles: IndexMap<String, Pile>,
     state: State,
     seed: String,
     error_message: Option<String>,
     pack_size: usize,
+    // The last shuffle and which of its packs are dealt, kept while going back to the piles but dropped once the config changes.
+    packs: Option<Vec<Pack<String>>>,
+    done: Option<Vec<bool>>,
+    dealing: Option<Order>,
+    history: History<Snapshot>,
 }
 
-fn get_seed(seed: &str) -> u64 {
-    match seed.parse::<u64>() {
-        Ok(s) => s,
-        Err(_) => {
-            let mut hasher = Sha3_256::new();
-            hasher.update(seed.as_bytes());
-            let full_hash = hasher.finalize();
-            let mut rdr = Cursor::new(full_hash);
-            rdr.read_u64::<BigEndian>().unwrap()
+impl App {
+    fn new() -> Self {
+        let mut rng = StdRng::from_entropy();
+        Self {
+            piles: IndexMap::new(),
+            state: State::Piling,
+            seed: rng.next_u64().to_string(),
+            error_message: None,
+            pack_size: 15,
+            packs: None,
+            done: None,
+            dealing: None,
+            history: History::default(),
+        }
+    }
+
+    fn stored() -> Self {
+        match storage::load() {
+            Some(saved) => {
+                let mut app = Self {
+                    piles: ordered_piles(saved.config.piles, saved.order),
+                    state: State::Piling,
+                    seed: saved.config.seed,
+                    error_message: None,
+                    pack_size: saved.config.pack_size,
+                    packs: None,
+                    // Other versions may shuffle the same config into other packs.
+                    done: saved.done.filter(|_| saved.config.version == VERSION),
+                    dealing: None,
+                    history: History::default(),
+                };
+                if saved.shuffled {
+                    app.shuffle();
+                }
+                app
+            }
+            None => App::new(),
         }
     }
+
+    fn from_shared(shared: Shared) -> Self {
+        let mut app = Self {
+            piles: shared.piles.into_iter().collect(),
+            state: State::Piling,
+            seed: shared.seed,
+            error_message: None,
+            pack_size: shared.pack_size,
+            packs: None,
+            done: None,
+            dealing: None,
+            history: History::default(),
+        };
+        if shared.shuffled {
+            app.shuffle();
+        }
+        app
+    }
+
+    // The shared config is saved at once, so reloading keeps it. A different cube saved in this browser is only
+    // replaced when confirmed, and stays one undo away.
+    fn open_shared(shared: Shared) -> Self {
+        let stored = App::stored();
+        let mut app = App::from_shared(shared);
+        if stored.piles.is_empty() || stored.config() == app.config() {
+            app.save();
+            return app;
+        }
+        if !gloo_dialogs::confirm(
+            "Do you want to replace the cube saved in this browser with the shared one?",
+        ) {
+            return stored;
+        }
+        app.history.record(stored.snapshot(), None);
+        app.save();
+        app
+    }
+
+    // Progress is only restored when it still fits the packs, otherwise dealing starts over.
+    fn shuffle(&mut self) {
+        let packs = match self
+            .packs
+            .take()
+            .map_or_else(|| distribute_shuffle(self), Ok)
+        {
+            Ok(packs) => packs,
+            Err(e) => {
+                self.done = None;
+                self.error_message = Some(e);
+                return;
+            }
+        };
+        if self.done.as_ref().map(Vec::len) != Some(packs.len()) {
+            self.done = Some(vec![false; packs.len()]);
+        }
+        self.packs = Some(packs);
+        self.state = State::Shuffled;
+    }
+
+    fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            piles: self.piles.clone(),
+            state: self.state,
+            seed: self.seed.clone(),
+            pack_size: self.pack_size,
+            packs: self.packs.clone(),
+            done: self.done.clone(),
+        }
+    }
+
+    fn restore(&mut self, snapshot: Snapshot) {
+        self.piles = snapshot.piles;
+        self.state = snapshot.state;
+        self.seed = snapshot.seed;
+        self.pack_size = snapshot.pack_size;
+        self.packs = snapshot.packs;
+        self.done = snapshot.done;
+        self.dealing = None;
+    }
+
+    fn share_link(&self, shuffled: bool) -> String {
+        let shared = Shared {
+            seed: self.seed.clone(),
+            pack_size: self.pack_size,
+            piles: self
+                .piles
+                .iter()
+                .map(|(name, pile)| (name.clone(), *pile))
+                .collect(),
+            shuffled,
+        };
+        let page = web_sys::window()
+            .and_then(|w| w.location().href().ok())
+            .unwrap_or_default();
+        let page = page.split('#').next().unwrap_or_default();
+        format!("{}#{}", page, share::encode(&shared))
+    }
+
+    fn config(&self) -> Config {
+        let piles: HashMap<String, Pile> = self
+            .piles
+            .iter()
+            .map(|(name, pile)| (name.clone(), *pile))
+            .collect();
+        Config::new(self.seed.clone(), self.pack_size, piles)
+    }
+
+    fn save(&self) {
+        storage::save(
+            &self.config(),
+            self.piles.keys().cloned().collect(),
+            self.done.clone(),
+            self.state == State::Shuffled,
+        );
+    }
+}
+
+// Piles missing from the saved order, such as from hand edited storage, are placed last in name order.
+fn ordered_piles(mut piles: HashMap<String, Pile>, order: Vec<String>) -> IndexMap<String, Pile> {
+    let mut ordered: IndexMap<String, Pile> = order
+        .into_iter()
+        .filter_map(|name| piles.remove(&name).map(|pile| (name, pile)))
+        .collect();
+    let mut rest: Vec<(String, Pile)> = piles.into_iter().collect();
+    rest.sort_by(|a, b| a.0.cmp(&b.0));
+    ordered.extend(rest);
+    ordered
+}
+
+fn replace_pile(name: &str) -> bool {
+    gloo_dialogs::confirm(&format!(
+        "A pile named \"{}\" already exists. Do you want to replace it?",
+        name
+    ))
+}
+
+// Asked once for all piles of an import, listing each pile which would be replaced.
+fn replace_piles(names: &[&str]) -> bool {
+    match names {
+        [name] => replace_pile(name),
+        _ => gloo_dialogs::confirm(&format!(
+            "The piles \"{}\" already exist. Do you want to replace them?",
+            names.join("\", \"")
+        )),
+    }
 }
 
 fn distribute_shuffle(app: &App) -> Result<Vec<Pack<String>>, String> {
@@ -62,33 +283,30 @@ fn distribute_shuffle(app: &App) -> Result<Vec<Pack<String>>, String> {
     if total_cards == 0 {
         return Err(String::from("All piles are empty."));
     }
-    let seed = get_seed(&app.seed);
-    let mut rng = StdRng::seed_from_u64(seed);
-    let packs =
-        match cube_shuffle_core::distribution_shuffle::shuffle(&app.piles, app.pack_size, &mut rng)
-        {
-            Ok(p) => match p.len() {
-                0 => {
-                    return Err(format!(
-                        "{} card(s) is not enough to fill a single pack of size {}.",
-                        total_cards, app.pack_size
-                    ))
-                }
-                _ => p,
-            },
-            Err(e) => {
-                return Err(match e {
-                    ShufflingErrors::EmptyPacks => String::from("Empty pack."),
-                    ShufflingErrors::CardOverflow {
-                        current_cards,
-                        max_cards,
-                    } => format!(
-                        "You have entered in total {} but your current build only supports {}.",
-                        current_cards, max_cards
-                    ),
-                });
+    let config = app.config();
+    let packs = match config.shuffle() {
+        Ok(p) => match p.len() {
+            0 => {
+                return Err(format!(
+                    "{} card(s) is not enough to fill a single pack of size {}.",
+                    total_cards, app.pack_size
+                ))
             }
-        };
+            _ => p,
+        },
+        Err(e) => {
+            return Err(match e {
+                ShufflingErrors::EmptyPacks => String::from("Empty pack."),
+                ShufflingErrors::CardOverflow {
+                    current_cards,
+                    max_cards,
+                } => format!(
+                    "You have entered in total {} but your current build only supports {}.",
+                    current_cards, max_cards
+                ),
+            });
+        }
+    };
     let owned_packs: Vec<Pack<String>> = packs
         .into_iter()
         .map(|pack| Pack {
@@ -102,28 +320,120 @@ fn distribute_shuffle(app: &App) -> Result<Vec<Pack<String>>, String> {
     Ok(owned_packs)
 }
 
+// Otherwise reloading the page would bring back the shared config over any later edits.
+fn clear_fragment() {
+    if let Some(window) = web_sys::window() {
+        let location = window.location();
+        let url = format!(
+            "{}{}",
+            location.pathname().unwrap_or_default(),
+            location.search().unwrap_or_default()
+        );
+        if let Ok(history) = window.history() {
+            history
+                .replace_state_with_url(&JsValue::NULL, "", Some(&url))
+                .ok();
+        }
+    }
+}
+
 impl Component for App {
     type Message = Msg;
     type Properties = ();
 
     fn create(_: &Context<Self>) -> Self {
-        let mut rng = StdRng::from_entropy();
-        Self {
-            piles: HashMap::new(),
-            state: State::Piling,
-            seed: rng.next_u64().to_string(),
-            error_message: None,
-            pack_size: 15,
+        let fragment = web_sys::window()
+            .and_then(|w| w.location().hash().ok())
+            .unwrap_or_default();
+        let shared = share::decode(&fragment);
+        if shared.is_some() {
+            clear_fragment();
+        }
+        match shared {
+            Some(Ok(shared)) => App::open_shared(shared),
+            Some(Err(e)) => Self {
+                error_message: Some(e),
+                ..App::stored()
+            },
+            None => App::stored(),
         }
     }
 
     fn update(&mut self, _: &Context<Self>, msg: Self::Message) -> bool {
         self.error_message = None;
-        match msg {
+        let config = self.config();
+        let before = self.snapshot();
+        let undoable = !matches!(
+            msg,
+            Msg::Check(_)
+                | Msg::Done(_)
+                | Msg::Deal(_)
+                | Msg::Error(_)
+                | Msg::Undo
+                | Msg::Redo
+                | Msg::Reset
+        );
+        let group = match msg {
+            Msg::UpdateSeed(_) => Some("seed"),
+            Msg::UpdatePackSize(_) => Some("pack_size"),
+            _ => None,
+        };
+        let changed = match msg {
             Msg::AddPile { name, pile } => {
+                if self.piles.contains_key(&name) && !replace_pile(&name) {
+                    return false;
+                }
                 self.piles.insert(name, pile);
                 true
             }
+            Msg::EditPile { old, name, pile } => {
+                if name != old && self.piles.contains_key(&name) && !replace_pile(&name) {
+                    return true;
+                }
+                self.piles = self
+                    .piles
+                    .drain(..)
+                    .filter(|(n, _)| *n == old || *n != name)
+                    .map(|(n, p)| match n == old {
+                        true => (name.clone(), pile),
+                        false => (n, p),
+                    })
+                    .collect();
+                true
+            }
+            Msg::ImportPiles(piles) => {
+                let imported = ordered_piles(piles, Vec::new());
+                let existing: Vec<&str> = imported
+                    .keys()
+                    .filter(|name| self.piles.contains_key(*name))
+                    .map(String::as_str)
+                    .collect();
+                if !existing.is_empty() && !replace_piles(&existing) {
+                    return false;
+                }
+                self.piles.extend(imported);
+                true
+            }
+            Msg::LoadConfig(config) => {
+                self.piles = ordered_piles(config.piles, Vec::new());
+                self.seed = config.seed;
+                self.pack_size = config.pack_size;
+                self.state = State::Piling;
+                true
+            }
+            Msg::UsePreset(template) => {
+                if !self.piles.is_empty()
+                    && !gloo_dialogs::confirm(&format!(
+                        "Do you want to replace the current piles with \"{}\"?",
+                        template.name
+                    ))
+                {
+                    return false;
+                }
+                self.piles = template.piles.into_iter().collect();
+                self.pack_size = template.pack_size;
+                true
+            }
             Msg::UpdateSeed(seed) => {
                 self.seed = seed;
                 true
@@ -136,24 +446,72 @@ impl Component for App {
             }
             Msg::Pile => {
                 self.state = State::Piling;
+                self.dealing = None;
                 true
             }
             Msg::Shuffle => {
-                match distribute_shuffle(self) {
-                    Ok(packs) => self.state = State::Shuffled { packs },
-                    Err(e) => self.error_message = Some(e),
+                self.shuffle();
+                true
+            }
+            Msg::Check(index) => {
+                if let Some(checked) = self.done.as_mut().and_then(|done| done.get_mut(index)) {
+                    *checked = !*checked;
+                }
+                true
+            }
+            Msg::Done(index) => {
+                if let Some(checked) = self.done.as_mut().and_then(|done| done.get_mut(index)) {
+                    *checked = true;
                 }
                 true
             }
+            Msg::Deal(order) => {
+                self.dealing = order;
+                true
+            }
             Msg::Error(e) => {
                 self.error_message = e;
                 true
             }
+            Msg::Undo => {
+                if let Some(snapshot) = self.history.undo(before.clone()) {
+                    self.restore(snapshot);
+                }
+                true
+            }
+            Msg::Redo => {
+                if let Some(snapshot) = self.history.redo(before.clone()) {
+                    self.restore(snapshot);
+                }
+                true
+            }
             Msg::DelPile(pile) => {
-                self.piles.remove(&pile);
+                self.piles.shift_remove(&pile);
                 true
             }
+            Msg::Reset => {
+                // Reset also clears the undo history, so there is no way back.
+                if !gloo_dialogs::confirm(
+                    "Do you want to delete all piles, packs and the undo history?",
+                ) {
+                    return false;
+                }
+                storage::clear();
+                *self = App::new();
+                return true;
+            }
+        };
+        if undoable {
+            if self.config() != config {
+                self.packs = None;
+                self.done = None;
+            }
+            if self.snapshot() != before {
+                self.history.record(before, group);
+            }
         }
+        self.save();
+        changed
     }
 
     fn view(&self, ctx: &Context<Self>) -> Html {
@@ -161,13 +519,37 @@ impl Component for App {
         let content = match &self.state {
             State::Piling => {
                 let add_pile = link.callback(|(name, pile)| Msg::AddPile { name, pile });
+                let import_piles = link.callback(Msg::ImportPiles);
                 let delete_pile = link.callback(Msg::DelPile);
+                let piles: Vec<(String, Pile)> = self
+                    .piles
+                    .iter()
+                    .map(|(name, pile)| (name.clone(), *pile))
+                    .collect();
+                let edit_pile =
+                    link.callback(|(old, name, pile)| Msg::EditPile { old, name, pile });
                 let update_seed = link.callback(Msg::UpdateSeed);
+                let dice_seed = link.callback(Msg::UpdateSeed);
                 let update_pack_size = link.callback(Msg::UpdatePackSize);
                 let to_shuffle = link.callback(|_| Msg::Shuffle);
+                let reset = link.callback(|_| Msg::Reset);
                 let on_error = link.callback(|e| Msg::Error(Some(e)));
+                let load_config = link.callback(Msg::LoadConfig);
+                let use_preset = link.callback(Msg::UsePreset);
+                let progress = self.done.as_ref().map_or(html! {}, |done| {
+                    html! {
+                        <div class="notification is-info is-light">
+                            { format!(
+                                "{}/{} packs are done. Changing the config restarts dealing.",
+                                done.iter().filter(|checked| **checked).count(),
+                                done.len()
+                            ) }
+                        </div>
+                    }
+                });
                 html! {
                     <>
+                        { progress }
                         <div class="columns is-multiline is-centered">
                             <div class="column is-narrow">
                                 <div class="field">
@@ -183,6 +565,7 @@ impl Component for App {
                                             }
                                         />
                                     </div>
+                                    <DiceSeed on_seed={ dice_seed }/>
                                 </div>
                                 <div class="field">
                                     <label class="label">{ "Pack size" }</label>
@@ -199,29 +582,73 @@ impl Component for App {
                                 </div>
                                 <div class="field">
                                     <div class="control">
-                                        <button class="button is-success" onclick={ to_shuffle }>{ "Generate packs" }</button>
+                                        <div class="buttons">
+                                            <button class="button is-success" onclick={ to_shuffle }>{ "Generate packs" }</button>
+                                            <a class="button is-link is-outlined" href={ self.share_link(false) }>{ "Share link" }</a>
+                                            <button class="button is-danger is-outlined" onclick={ reset }>{ "Reset" }</button>
+                                        </div>
                                     </div>
                                 </div>
                             </div>
                             <div class="column is-narrow">
-                                <AddPile { on_error } on_add={ add_pile }/>
+                                <Presets piles={ piles.clone() } pack_size={ self.pack_size } on_use={ use_preset }/>
+                            </div>
+                            <div class="column is-narrow">
+                                <AddPile on_error={ on_error.clone() } on_add={ add_pile }/>
+                            </div>
+                            <div class="column is-narrow">
+                                <ImportPiles on_error={ on_error.clone() } on_import={ import_piles }/>
+                                <ConfigFile config={ self.config() } on_load={ load_config } { on_error }/>
                             </div>
                         </div>
-                        <PileList piles={ self.piles.to_owned() } { delete_pile }/>
+                        <SummaryPanel piles={ piles.clone() } pack_size={ self.pack_size }/>
+                        <PileList { piles } { delete_pile } { edit_pile }/>
                     </>
                 }
             }
-            State::Shuffled { packs } => {
+            State::Shuffled => {
+                let packs = self.packs.clone().unwrap_or_default();
                 let to_pile = link.callback(|_| Msg::Pile);
+                let check = link.callback(Msg::Check);
+                let done = self.done.clone().unwrap_or_default();
+                let dealer_mode = self.dealing.map_or(html! {}, |order| {
+                    let on_done = link.callback(Msg::Done);
+                    let on_close = link.callback(|_| Msg::Deal(None));
+                    html! {
+                        <DealerMode packs={ packs.clone() } done={ done.clone() } { order } { on_done } { on_close }/>
+                    }
+                });
+                let deal_packs = link.callback(|_| Msg::Deal(Some(Order::PackFirst)));
+                let deal_piles = link.callback(|_| Msg::Deal(Some(Order::PileFirst)));
+                let report = format!(
+                    "data:text/html;charset=utf-8,{}",
+                    js_sys::encode_uri_component(&html_report(&self.config(), &packs))
+                );
                 html! {
                     <>
-                        <button class="button is-danger" onclick={ to_pile }>{ "Back" }</button>
-                        <PackList packs={ packs.clone() }/>
+                        <div class="buttons">
+                            <button class="button is-danger" onclick={ to_pile }>{ "Back" }</button>
+                            <a class="button is-info" href={ report } download="cube_shuffle_report.html">{ "Save report" }</a>
+                            <a class="button is-link is-outlined" href={ self.share_link(true) }>{ "Share link" }</a>
+                            <button class="button is-primary" onclick={ deal_packs }>{ "Deal pack by pack" }</button>
+                            <button class="button is-primary is-outlined" onclick={ deal_piles }>{ "Deal pile by pile" }</button>
+                        </div>
+                        { dealer_mode }
+                        <PackList { packs } { done } on_check={ check }/>
+                        <div class="columns is-centered">
+                            <div class="column is-narrow">
+                                <SeatingPanel seed={ parse_seed(&self.seed) }/>
+                            </div>
+                        </div>
                     </>
                 }
             }
         };
 
+        let undo = link.callback(|_| Msg::Undo);
+        let redo = link.callback(|_| Msg::Redo);
+        let on_undo = link.callback(|_| Msg::Undo);
+        let on_redo = link.callback(|_| Msg::Redo);
         let clear_error = link.callback(|_| Msg::Error(None));
         let error_html: Html = self.error_message.clone().map_or(html! {}, |e| {
             html! {
@@ -247,33 +674,13 @@ impl Component for App {
                     </div>
                 </section>
                 { error_html }
+                <Shortcuts { on_undo } { on_redo }/>
+                <div class="buttons are-small">
+                    <button class="button" title="Ctrl+Z" onclick={ undo } disabled={ !self.history.can_undo() }>{ "Undo" }</button>
+                    <button class="button" title="Ctrl+Y" onclick={ redo } disabled={ !self.history.can_redo() }>{ "Redo" }</button>
+                </div>
                 { content }
             </>
         }
     }
 }
-
-#[cfg(test)]
-mod tests {
-    use crate::components::app::get_seed;
-    use itertools::Itertools;
-    use proptest::proptest;
-
-    proptest! {
-        #[test]
-        fn get_seed_u64(seed:u64){
-            assert_eq!(seed, get_seed(&seed.to_string()));
-        }
-
-        #[test]
-        fn get_seed_str(seed:String) {
-            get_seed(&seed);
-        }
-    }
-
-    #[test]
-    fn get_seed_unique() {
-        let unique = ('A'..'z').map(|c| get_seed(&c.to_string())).all_unique();
-        assert!(unique);
-    }
-}
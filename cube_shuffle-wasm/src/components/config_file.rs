@@ -0,0 +1,143 @@
+use gloo_file::callbacks::{read_as_text, FileReader};
+use gloo_file::File;
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+
+use cube_shuffle_core::config::{Config, ConfigErrors};
+
+pub enum Msg {
+    Select(Option<web_sys::File>),
+    Loaded(Result<String, String>),
+}
+
+#[derive(PartialEq, Properties)]
+pub struct Props {
+    pub config: Config,
+    pub on_load: Callback<Config>,
+    pub on_error: Callback<String>,
+}
+
+pub struct ConfigFile {
+    reader: Option<FileReader>,
+}
+
+fn config_error(error: ConfigErrors) -> String {
+    match error {
+        ConfigErrors::EmptyPacks => String::from("The config has a pack size of 0."),
+        ConfigErrors::InvalidRandomness { pile } => {
+            format!(
+                "The randomness of pile \"{}\" is not between 0% and 100%.",
+                pile
+            )
+        }
+    }
+}
+
+// Files are the same YAML or JSON as written by the CLI with --save-config, read as YAML since JSON is valid YAML.
+fn parse_config(text: &str) -> Result<Config, String> {
+    let config: Config = serde_yaml::from_str(text)
+        .map_err(|e| format!("The file is not a CubeShuffle config: {}", e))?;
+    config.validate().map_err(config_error)?;
+    Ok(config)
+}
+
+impl Component for ConfigFile {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(_: &Context<Self>) -> Self {
+        Self { reader: None }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::Select(file) => {
+                if let Some(file) = file {
+                    let link = ctx.link().clone();
+                    self.reader = Some(read_as_text(&File::from(file), move |text| {
+                        link.send_message(Msg::Loaded(text.map_err(|e| e.to_string())))
+                    }));
+                }
+                false
+            }
+            Msg::Loaded(text) => {
+                self.reader = None;
+                match text.and_then(|t| parse_config(&t)) {
+                    Ok(config) => ctx.props().on_load.emit(config),
+                    Err(e) => ctx.props().on_error.emit(e),
+                }
+                false
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let select = ctx.link().callback(|e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            Msg::Select(input.files().and_then(|files| files.get(0)))
+        });
+        let export = format!(
+            "data:application/json;charset=utf-8,{}",
+            js_sys::encode_uri_component(
+                &serde_json::to_string_pretty(&ctx.props().config).unwrap()
+            )
+        );
+        html! {
+            <div class="buttons">
+                <a class="button is-info is-outlined" href={ export } download="cube.json">{ "Export config" }</a>
+                <div class="file">
+                    <label class="file-label">
+                        <input class="file-input" type="file" accept=".json,.yaml,.yml,application/json,application/yaml" onchange={ select }/>
+                        <span class="file-cta">
+                            <span class="file-label">{ "Import config" }</span>
+                        </span>
+                    </label>
+                </div>
+            </div>
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use cube_shuffle_core::config::Config;
+    use cube_shuffle_core::distribution_shuffle::Pile;
+
+    use crate::components::config_file::parse_config;
+
+    #[test]
+    fn invalid_configs() {
+        assert!(parse_config("{}").is_err());
+        assert!(parse_config(
+            r#"{"seed":"1","pack_size":15,"piles":{"Red":{"cards":5,"randomness":2.0}}}"#
+        )
+        .is_err());
+        assert_eq!(
+            15,
+            parse_config(r#"{"seed":"1","pack_size":15,"piles":{}}"#)
+                .unwrap()
+                .pack_size
+        );
+    }
+
+    #[test]
+    fn cli_configs() {
+        let config = Config::new(
+            String::from("Friday"),
+            15,
+            HashMap::from([(
+                String::from("Red"),
+                Pile {
+                    cards: 45,
+                    randomness: 0.1,
+                },
+            )]),
+        );
+        let yaml = serde_yaml::to_string(&config).unwrap();
+        assert_eq!(config, parse_config(&yaml).unwrap());
+        let json = serde_json::to_string_pretty(&config).unwrap();
+        assert_eq!(config, parse_config(&json).unwrap());
+    }
+}
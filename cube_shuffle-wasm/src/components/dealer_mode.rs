@@ -0,0 +1,158 @@
+use gloo_events::EventListener;
+use web_sys::HtmlElement;
+use yew::prelude::*;
+
+use cube_shuffle_core::distribution_shuffle::Pack;
+
+use crate::dealing::{completed_pack, steps, Order, Step};
+
+pub enum Msg {
+    Next,
+    Previous,
+    Close,
+}
+
+#[derive(PartialEq, Properties)]
+pub struct Props {
+    pub packs: Vec<Pack<String>>,
+    pub done: Vec<bool>,
+    pub order: Order,
+    pub on_done: Callback<usize>,
+    pub on_close: Callback<()>,
+}
+
+pub struct DealerMode {
+    // Fixed when dealing starts, so marking packs as done does not move the current step.
+    steps: Vec<Step>,
+    step: usize,
+    node: NodeRef,
+    // Cordova only hands the volume keys to the page as these document events, and only while they are listened to.
+    _volume_keys: Vec<EventListener>,
+}
+
+fn instruction(step: &Step) -> String {
+    let cards = match step.cards {
+        1 => String::from("1 card"),
+        n => format!("{} cards", n),
+    };
+    format!("{} → Pack {}: {}", step.pile, step.pack + 1, cards)
+}
+
+impl Component for DealerMode {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let props = ctx.props();
+        let volume_keys = web_sys::window()
+            .and_then(|w| w.document())
+            .map(|document| {
+                [("volumeupbutton", true), ("volumedownbutton", false)]
+                    .into_iter()
+                    .map(|(event, next)| {
+                        let link = ctx.link().clone();
+                        EventListener::new(&document, event, move |_| {
+                            link.send_message(match next {
+                                true => Msg::Next,
+                                false => Msg::Previous,
+                            })
+                        })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        Self {
+            steps: steps(&props.packs, &props.done, props.order),
+            step: 0,
+            node: NodeRef::default(),
+            _volume_keys: volume_keys,
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::Next => {
+                if self.step >= self.steps.len() {
+                    return false;
+                }
+                if let Some(pack) = completed_pack(&self.steps, self.step) {
+                    ctx.props().on_done.emit(pack);
+                }
+                self.step += 1;
+                true
+            }
+            Msg::Previous => {
+                self.step = self.step.saturating_sub(1);
+                true
+            }
+            Msg::Close => {
+                ctx.props().on_close.emit(());
+                false
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let link = ctx.link();
+        let next = link.callback(|_| Msg::Next);
+        // Desktop browsers may send media keys as key presses, the Android app gets the volume keys from Cordova.
+        let key = link.batch_callback(|e: KeyboardEvent| {
+            let msg = match e.key().as_str() {
+                " " | "Enter" | "ArrowRight" | "AudioVolumeUp" => Msg::Next,
+                "Backspace" | "ArrowLeft" | "AudioVolumeDown" => Msg::Previous,
+                "Escape" => Msg::Close,
+                _ => return None,
+            };
+            e.prevent_default();
+            Some(msg)
+        });
+        let previous = link.callback(|e: MouseEvent| {
+            e.stop_propagation();
+            Msg::Previous
+        });
+        let close = link.callback(|e: MouseEvent| {
+            e.stop_propagation();
+            Msg::Close
+        });
+        let (current, upcoming) = match self.steps.get(self.step) {
+            Some(step) => (
+                instruction(step),
+                self.steps
+                    .get(self.step + 1)
+                    .map_or_else(String::new, |s| format!("Next: {}", instruction(s))),
+            ),
+            None => (String::from("All packs are dealt."), String::new()),
+        };
+        html! {
+            <div class="modal is-active">
+                <div class="modal-background has-background-black"></div>
+                <div
+                    class="modal-content has-text-centered"
+                    style="width: 100vw; height: 100vh; display: flex; flex-direction: column; justify-content: center; outline: none;"
+                    tabindex="0"
+                    ref={ self.node.clone() }
+                    onclick={ next }
+                    onkeydown={ key }
+                >
+                    <p class="subtitle has-text-grey-light">
+                        { format!("{}/{}", self.step.min(self.steps.len()), self.steps.len()) }
+                    </p>
+                    <p class="title is-1 has-text-white">{ current }</p>
+                    <p class="subtitle has-text-grey-light">{ upcoming }</p>
+                    <div class="buttons is-centered">
+                        <button class="button is-dark" onclick={ previous }>{ "Previous" }</button>
+                        <button class="button is-danger" onclick={ close }>{ "Close" }</button>
+                    </div>
+                </div>
+            </div>
+        }
+    }
+
+    fn rendered(&mut self, _: &Context<Self>, first_render: bool) {
+        if first_render {
+            if let Some(node) = self.node.cast::<HtmlElement>() {
+                node.focus().ok();
+            }
+        }
+    }
+}
@@ -0,0 +1,105 @@
+use yew::prelude::*;
+
+use cube_shuffle_core::dice::{dice_seed, parse_rolls, DiceErrors, DiceSeed as Entropy, SEED_BITS};
+
+use crate::components::integer_input::IntegerInput;
+use crate::components::text_input::TextInput;
+
+pub enum Msg {
+    UpdateSides(Option<i128>),
+    UpdateRolls(String),
+    UseSeed(u64),
+}
+
+#[derive(PartialEq, Properties)]
+pub struct Props {
+    pub on_seed: Callback<String>,
+}
+
+pub struct DiceSeed {
+    sides: u32,
+    rolls: String,
+}
+
+fn dice_error(error: DiceErrors) -> String {
+    match error {
+        DiceErrors::InvalidDie { sides } => format!("A die needs at least 2 sides, not {}.", sides),
+        DiceErrors::InvalidRoll { roll, sides } => {
+            format!("{} can not be rolled with a {} sided die.", roll, sides)
+        }
+        DiceErrors::UnreadableRoll { roll } => format!("\"{}\" is not a roll.", roll),
+    }
+}
+
+impl Component for DiceSeed {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(_: &Context<Self>) -> Self {
+        Self {
+            sides: 6,
+            rolls: String::new(),
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::UpdateSides(sides) => {
+                self.sides = sides.and_then(|s| u32::try_from(s).ok()).unwrap_or(0);
+                true
+            }
+            Msg::UpdateRolls(rolls) => {
+                self.rolls = rolls;
+                true
+            }
+            Msg::UseSeed(seed) => {
+                ctx.props().on_seed.emit(seed.to_string());
+                self.rolls = String::new();
+                true
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let update_sides = ctx.link().callback(Msg::UpdateSides);
+        let update_rolls = ctx.link().callback(Msg::UpdateRolls);
+        let status = match parse_rolls(&self.rolls).and_then(|r| dice_seed(self.sides, &r)) {
+            Err(e) => html! { <p class="help is-danger">{ dice_error(e) }</p> },
+            Ok(Entropy {
+                seed: Some(seed), ..
+            }) => {
+                let use_seed = ctx.link().callback(move |_| Msg::UseSeed(seed));
+                html! {
+                    <button class="button is-success is-small" onclick={ use_seed }>{ "Use as seed" }</button>
+                }
+            }
+            Ok(Entropy {
+                bits, rolls_needed, ..
+            }) => html! {
+                <p class="help">{ format!("{} of {} bits, roll about {} more times.", bits, SEED_BITS, rolls_needed) }</p>
+            },
+        };
+        html! {
+            <details>
+                <summary>{ "Roll dice" }</summary>
+                <div class="field">
+                    <label class="label">{ "Sides" }</label>
+                    <div class="control">
+                        <IntegerInput min=2 max={ i128::from(u32::MAX) } on_change={ update_sides } value={ i128::from(self.sides) } placeholder={ "Sides of the die" }
+                            tooltip="The number of sides of the physical die, such as 6 or 20."
+                        />
+                    </div>
+                </div>
+                <div class="field">
+                    <label class="label">{ "Rolls" }</label>
+                    <div class="control">
+                        <TextInput on_change={ update_rolls } value={ self.rolls.clone() } placeholder={ "3 6 1 4 ..." }
+                            tooltip="The rolled numbers, separated by spaces or commas."
+                        />
+                    </div>
+                    { status }
+                </div>
+            </details>
+        }
+    }
+}
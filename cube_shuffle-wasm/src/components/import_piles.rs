@@ -0,0 +1,142 @@
+use std::collections::HashMap;
+
+use gloo_file::callbacks::{read_as_text, FileReader};
+use gloo_file::File;
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+
+use cube_shuffle_core::distribution_shuffle::{Odds, Pile};
+use cube_shuffle_core::import::{group, parse, piles, ImportErrors};
+
+use crate::components::integer_input::IntegerInput;
+use crate::components::text_input::TextInput;
+
+pub enum Msg {
+    UpdateGroupBy(String),
+    UpdateRandomness(Option<i128>),
+    Select(Option<web_sys::File>),
+    Loaded(Result<String, String>),
+}
+
+#[derive(PartialEq, Properties)]
+pub struct Props {
+    pub on_import: Callback<HashMap<String, Pile>>,
+    pub on_error: Callback<String>,
+}
+
+pub struct ImportPiles {
+    group_by: String,
+    randomness: i128,
+    reader: Option<FileReader>,
+}
+
+fn import_error(error: ImportErrors) -> String {
+    match error {
+        ImportErrors::EmptyList => String::from("The cube list contains no cards."),
+        ImportErrors::MissingNameColumn => String::from("The CSV file has no \"name\" column."),
+        ImportErrors::UnclosedQuote => String::from("The CSV file has an unclosed quote."),
+        ImportErrors::InvalidQuantity { line } => {
+            format!("Invalid card quantity on line {}.", line)
+        }
+        ImportErrors::UnknownColumn { column } => {
+            format!("The cube list has no \"{}\" column.", column)
+        }
+    }
+}
+
+impl Component for ImportPiles {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(_: &Context<Self>) -> Self {
+        Self {
+            group_by: String::new(),
+            randomness: 10,
+            reader: None,
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::UpdateGroupBy(group_by) => {
+                self.group_by = group_by;
+                false
+            }
+            Msg::UpdateRandomness(randomness) => {
+                self.randomness = match randomness {
+                    None => 0,
+                    Some(r) => r.clamp(0, 100),
+                };
+                false
+            }
+            Msg::Select(file) => {
+                if let Some(file) = file {
+                    let link = ctx.link().clone();
+                    self.reader = Some(read_as_text(&File::from(file), move |text| {
+                        link.send_message(Msg::Loaded(text.map_err(|e| e.to_string())))
+                    }));
+                }
+                false
+            }
+            Msg::Loaded(text) => {
+                self.reader = None;
+                let group_by = Some(self.group_by.trim()).filter(|g| !g.is_empty());
+                let imported = text.and_then(|t| {
+                    parse(&t)
+                        .and_then(|cards| group(&cards, group_by))
+                        .map_err(import_error)
+                });
+                match imported {
+                    Ok(groups) => ctx
+                        .props()
+                        .on_import
+                        .emit(piles(&groups, (self.randomness as Odds) * 0.01)),
+                    Err(e) => ctx.props().on_error.emit(e),
+                }
+                false
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let update_group_by = ctx.link().callback(Msg::UpdateGroupBy);
+        let update_randomness = ctx.link().callback(Msg::UpdateRandomness);
+        let select = ctx.link().callback(|e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            Msg::Select(input.files().and_then(|files| files.get(0)))
+        });
+        html! {
+            <>
+                <div class="field">
+                    <label class="label">{ "Group by" }</label>
+                    <div class="control">
+                        <TextInput on_change={ update_group_by } value={ self.group_by.clone() } placeholder={ "Color Category" }
+                            tooltip={
+                                "The column of the cube list to group the cards into piles by, such as \"Color\", \"Type\" or \"tags\".\n\
+                                Leave empty for the color category of CSV files or the \"//\" sections of plain text lists.\n"
+                            }
+                        />
+                    </div>
+                </div>
+                <div class="field">
+                    <label class="label">{ "Randomness" }</label>
+                    <div class="control">
+                        <IntegerInput min=0 max=100 on_change={ update_randomness } step=5 value={ self.randomness } placeholder={ "Percentage of randomness" }
+                            tooltip="The randomness of the imported piles."
+                        />
+                    </div>
+                </div>
+                <div class="field">
+                    <div class="file">
+                        <label class="file-label">
+                            <input class="file-input" type="file" accept=".csv,.txt,text/csv,text/plain" onchange={ select }/>
+                            <span class="file-cta">
+                                <span class="file-label">{ "Import cube list" }</span>
+                            </span>
+                        </label>
+                    </div>
+                </div>
+            </>
+        }
+    }
+}
@@ -5,75 +5,46 @@ use cube_shuffle_core::distribution_shuffle::Pack;
 
 use crate::components::pack_card::PackCard;
 
-#[derive(Clone, PartialEq, Eq, Properties)]
+#[derive(Clone, PartialEq, Properties)]
 pub struct Props {
     pub packs: Vec<Pack<String>>,
+    pub done: Vec<bool>,
+    pub on_check: Callback<usize>,
 }
 
-pub struct PackItem {
-    pub pack: Pack<String>,
-    pub checked: bool,
-}
-
-pub enum Msg {
-    Check(usize),
-}
-
-pub struct PackList {
-    packs: Vec<PackItem>,
-}
-
-impl Component for PackList {
-    type Message = Msg;
-    type Properties = Props;
-
-    fn create(ctx: &Context<Self>) -> Self {
-        Self {
-            packs: ctx
-                .props()
-                .packs
-                .iter()
-                .map(|pack| PackItem {
-                    pack: pack.to_owned(),
-                    checked: false,
-                })
-                .collect(),
-        }
-    }
-
-    fn update(&mut self, _: &Context<Self>, msg: Self::Message) -> bool {
-        match msg {
-            Msg::Check(index) => {
-                self.packs[index].checked = !(self.packs[index].checked);
-                true
+#[function_component(PackList)]
+pub fn pack_list(props: &Props) -> Html {
+    let packs: Html = props
+        .packs
+        .iter()
+        .zip(props.done.iter().copied())
+        .enumerate()
+        .sorted_by_key(|(_, (_, checked))| *checked)
+        .map(|(index, (pack, checked))| {
+            html! {
+                <div class="column is-narrow">
+                    <PackCard
+                        index={ index }
+                        pack={ pack.clone() }
+                        checked={ checked }
+                        onclick={ &props.on_check }
+                    />
+                </div>
             }
-        }
-    }
-
-    fn view(&self, ctx: &Context<Self>) -> Html {
-        let packs: Html = self
-            .packs
-            .iter()
-            .enumerate()
-            .sorted_by_key(|(_, pi)| pi.checked)
-            .map(|(index, pack_item)| {
-                let on_click = ctx.link().callback(Msg::Check);
-                html! {
-                    <div class="column is-narrow">
-                        <PackCard
-                            index={ index }
-                            pack={ pack_item.pack.clone() }
-                            checked={ pack_item.checked }
-                            onclick={ on_click }
-                        />
-                    </div>
-                }
-            })
-            .collect();
-        html! {
+        })
+        .collect();
+    let done = props.done.iter().filter(|checked| **checked).count();
+    html! {
+        <>
+            <div class="columns is-centered">
+                <div class="column is-half">
+                    <p class="has-text-centered">{ format!("{}/{} done", done, props.packs.len()) }</p>
+                    <progress class="progress is-success" value={ done.to_string() } max={ props.packs.len().to_string() }/>
+                </div>
+            </div>
             <div class="columns is-multiline is-centered">
                 { packs }
             </div>
-        }
+        </>
     }
 }
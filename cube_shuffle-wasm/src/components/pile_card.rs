@@ -1,26 +1,50 @@
 use yew::prelude::*;
 
-use cube_shuffle_core::distribution_shuffle::Pile;
+use cube_shuffle_core::distribution_shuffle::{Odds, Pile};
+
+use crate::components::integer_input::IntegerInput;
+use crate::components::text_input::TextInput;
 
 #[derive(Clone, PartialEq, Properties)]
 pub struct Props {
     pub name: String,
     pub pile: Pile,
     pub delete: Callback<String>,
+    // The old name, the new name and the new pile.
+    pub edit: Callback<(String, String, Pile)>,
 }
 
 pub enum Msg {
     Delete,
+    Edit,
+    Cancel,
+    Save,
+    UpdateName(String),
+    UpdateCards(Option<i128>),
+    UpdateRandomness(Option<i128>),
 }
 
-pub struct PileCard {}
+pub struct PileCard {
+    editing: bool,
+    // The new name of a rename the parent has not accepted yet.
+    renaming: Option<String>,
+    name: String,
+    cards: usize,
+    randomness: i128,
+}
 
 impl Component for PileCard {
     type Message = Msg;
     type Properties = Props;
 
     fn create(_: &Context<Self>) -> Self {
-        Self {}
+        Self {
+            editing: false,
+            renaming: None,
+            name: String::new(),
+            cards: 0,
+            randomness: 0,
+        }
     }
 
     fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
@@ -30,12 +54,100 @@ impl Component for PileCard {
                 props.delete.emit(props.name.to_owned());
                 true
             }
+            Msg::Edit => {
+                self.editing = true;
+                self.renaming = None;
+                self.name = props.name.clone();
+                self.cards = props.pile.cards;
+                self.randomness = (props.pile.randomness * 100.0).round() as i128;
+                true
+            }
+            Msg::Cancel => {
+                self.editing = false;
+                self.renaming = None;
+                true
+            }
+            Msg::Save => {
+                if self.name.is_empty() {
+                    return false;
+                }
+                let pile = Pile {
+                    cards: self.cards,
+                    randomness: (self.randomness as Odds) * 0.01,
+                };
+                // A rename may be refused for a duplicate name, so the editor stays open until the new name arrives.
+                match self.name == props.name {
+                    true => self.editing = false,
+                    false => self.renaming = Some(self.name.clone()),
+                }
+                props
+                    .edit
+                    .emit((props.name.clone(), self.name.clone(), pile));
+                true
+            }
+            Msg::UpdateName(name) => {
+                self.name = name;
+                false
+            }
+            Msg::UpdateCards(cards) => {
+                self.cards = cards.map_or(0, |i| usize::try_from(i).unwrap_or(0));
+                false
+            }
+            Msg::UpdateRandomness(randomness) => {
+                self.randomness = match randomness {
+                    None => 0,
+                    Some(r) => r.clamp(0, 100),
+                };
+                false
+            }
         }
     }
 
+    fn changed(&mut self, ctx: &Context<Self>, _: &Self::Properties) -> bool {
+        if self.renaming.as_ref() == Some(&ctx.props().name) {
+            self.editing = false;
+            self.renaming = None;
+        }
+        true
+    }
+
     fn view(&self, ctx: &Context<Self>) -> Html {
         let props = ctx.props();
         let delete = ctx.link().callback(|_| Msg::Delete);
+        if self.editing {
+            let update_name = ctx.link().callback(Msg::UpdateName);
+            let update_cards = ctx.link().callback(Msg::UpdateCards);
+            let update_randomness = ctx.link().callback(Msg::UpdateRandomness);
+            let save = ctx.link().callback(|_| Msg::Save);
+            let cancel = ctx.link().callback(|_| Msg::Cancel);
+            return html! {
+                <article class="message is-medium">
+                    <div class="message-header">
+                        <TextInput on_change={ update_name } value={ self.name.clone() } placeholder={ "Name of the pile" }/>
+                        <button class="delete" onclick={ delete }></button>
+                    </div>
+                    <div class="message-body has-background-white">
+                        <div class="field">
+                            <label class="label">{ "Cards" }</label>
+                            <div class="control">
+                                <IntegerInput min=0 on_change={ update_cards } step=1 value={ self.cards as i128 } placeholder={ "Number of cards in pile" }/>
+                            </div>
+                        </div>
+                        <div class="field">
+                            <label class="label">{ "Randomness" }</label>
+                            <div class="control">
+                                <IntegerInput min=0 max=100 on_change={ update_randomness } step=5 value={ self.randomness } placeholder={ "Percentage of randomness" }/>
+                            </div>
+                        </div>
+                        <div class="buttons">
+                            <button class="button is-primary" onclick={ save }>{ "Save" }</button>
+                            <button class="button" onclick={ cancel }>{ "Cancel" }</button>
+                        </div>
+                    </div>
+                </article>
+            };
+        }
+        let edit = ctx.link().callback(|_| Msg::Edit);
         let pile = props.pile;
         let randomness = pile.randomness * 100.0;
         html! {
@@ -57,6 +169,7 @@ impl Component for PileCard {
                             </tr>
                         </tbody>
                     </table>
+                    <button class="button is-small is-fullwidth" onclick={ edit }>{ "Edit" }</button>
                 </div>
             </article>
         }
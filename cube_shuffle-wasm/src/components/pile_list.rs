@@ -1,13 +1,13 @@
 use cube_shuffle_core::distribution_shuffle::Pile;
-use std::collections::HashMap;
 use yew::prelude::*;
 
 use crate::components::pile_card::PileCard;
 
 #[derive(Clone, PartialEq, Properties)]
 pub struct Props {
-    pub piles: HashMap<String, Pile>,
+    pub piles: Vec<(String, Pile)>,
     pub delete_pile: Callback<String>,
+    pub edit_pile: Callback<(String, String, Pile)>,
 }
 
 #[function_component(PileList)]
@@ -16,12 +16,14 @@ pub fn pile_card(props: &Props) -> Html {
         .piles
         .iter()
         .map(|(name, pile)| {
+            // Keyed by name, so removing a pile does not hand its neighbour's open editor to another pile.
             html! {
-                <div class="column is-narrow">
+                <div class="column is-narrow" key={ name.clone() }>
                     <PileCard
                         name={ name.clone() }
                         pile={ *pile }
                         delete={ &props.delete_pile }
+                        edit={ &props.edit_pile }
                     />
                 </div>
             }
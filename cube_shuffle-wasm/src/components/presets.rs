@@ -0,0 +1,182 @@
+use web_sys::HtmlSelectElement;
+use yew::prelude::*;
+
+use cube_shuffle_core::distribution_shuffle::Pile;
+
+use crate::components::text_input::TextInput;
+use crate::presets::{builtin, Template};
+use crate::storage;
+
+pub enum Msg {
+    Select(usize),
+    Use,
+    UpdateName(String),
+    Save,
+    Delete,
+}
+
+#[derive(PartialEq, Properties)]
+pub struct Props {
+    pub piles: Vec<(String, Pile)>,
+    pub pack_size: usize,
+    pub on_use: Callback<Template>,
+}
+
+pub struct Presets {
+    builtin: Vec<Template>,
+    // Templates saved by the user, listed after the built in presets.
+    saved: Vec<Template>,
+    selected: usize,
+    name: String,
+}
+
+impl Presets {
+    fn selected(&self) -> Option<&Template> {
+        self.builtin
+            .iter()
+            .chain(self.saved.iter())
+            .nth(self.selected)
+    }
+
+    fn selected_saved(&self) -> Option<usize> {
+        self.selected
+            .checked_sub(self.builtin.len())
+            .filter(|index| *index < self.saved.len())
+    }
+}
+
+impl Component for Presets {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(_: &Context<Self>) -> Self {
+        Self {
+            builtin: builtin(),
+            saved: storage::load_templates(),
+            selected: 0,
+            name: String::new(),
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        let props = ctx.props();
+        match msg {
+            Msg::Select(selected) => {
+                self.selected = selected;
+                true
+            }
+            Msg::Use => {
+                if let Some(template) = self.selected() {
+                    props.on_use.emit(template.clone());
+                }
+                false
+            }
+            Msg::UpdateName(name) => {
+                self.name = name;
+                false
+            }
+            Msg::Save => {
+                let name = self.name.trim().to_owned();
+                if name.is_empty() || props.piles.is_empty() {
+                    return false;
+                }
+                let template = Template {
+                    name,
+                    pack_size: props.pack_size,
+                    piles: props.piles.clone(),
+                };
+                match self.saved.iter().position(|t| t.name == template.name) {
+                    Some(index) => {
+                        if !gloo_dialogs::confirm(&format!(
+                            "A template named \"{}\" already exists. Do you want to replace it?",
+                            template.name
+                        )) {
+                            return false;
+                        }
+                        self.saved[index] = template;
+                        self.selected = self.builtin.len() + index;
+                    }
+                    None => {
+                        self.saved.push(template);
+                        self.selected = self.builtin.len() + self.saved.len() - 1;
+                    }
+                }
+                storage::save_templates(&self.saved);
+                self.name = String::new();
+                true
+            }
+            Msg::Delete => {
+                if let Some(index) = self.selected_saved() {
+                    self.saved.remove(index);
+                    storage::save_templates(&self.saved);
+                    self.selected = 0;
+                }
+                true
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let link = ctx.link();
+        let select = link.callback(|e: Event| {
+            let select: HtmlSelectElement = e.target_unchecked_into();
+            Msg::Select(select.selected_index().try_into().unwrap_or(0))
+        });
+        let options: Html = self
+            .builtin
+            .iter()
+            .chain(self.saved.iter())
+            .enumerate()
+            .map(|(index, template)| {
+                let cards: usize = template.piles.iter().map(|(_, pile)| pile.cards).sum();
+                html! {
+                    <option selected={ index == self.selected }>
+                        { format!("{} ({} cards)", template.name, cards) }
+                    </option>
+                }
+            })
+            .collect();
+        let use_preset = link.callback(|_| Msg::Use);
+        let update_name = link.callback(Msg::UpdateName);
+        let save = link.callback(|_| Msg::Save);
+        let delete = match self.selected_saved() {
+            Some(_) => {
+                let delete = link.callback(|_| Msg::Delete);
+                html! {
+                    <button class="button is-danger is-outlined" onclick={ delete }>{ "Delete template" }</button>
+                }
+            }
+            None => html! {},
+        };
+        html! {
+            <>
+                <div class="field">
+                    <label class="label">{ "Presets" }</label>
+                    <div class="control">
+                        <div class="select">
+                            <select onchange={ select }>
+                                { options }
+                            </select>
+                        </div>
+                    </div>
+                </div>
+                <div class="field">
+                    <div class="buttons">
+                        <button class="button is-primary" onclick={ use_preset }>{ "Use preset" }</button>
+                        { delete }
+                    </div>
+                </div>
+                <div class="field has-addons">
+                    <div class="control">
+                        <TextInput on_change={ update_name } value={ self.name.clone() } placeholder={ "Template name" }
+                            tooltip="Saves the current piles and pack size as a template in this browser."
+                        />
+                    </div>
+                    <div class="control">
+                        <button class="button is-info" onclick={ save }>{ "Save template" }</button>
+                    </div>
+                </div>
+            </>
+        }
+    }
+}
@@ -0,0 +1,135 @@
+use yew::prelude::*;
+
+use cube_shuffle_core::seating::{seat, SeatingErrors};
+
+use crate::components::integer_input::IntegerInput;
+use crate::components::text_input::TextInput;
+
+pub enum Msg {
+    UpdatePlayers(String),
+    UpdateTeams(Option<i128>),
+}
+
+#[derive(PartialEq, Eq, Properties)]
+pub struct Props {
+    pub seed: u64,
+}
+
+pub struct SeatingPanel {
+    players: String,
+    teams: Option<usize>,
+}
+
+impl Component for SeatingPanel {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(_: &Context<Self>) -> Self {
+        Self {
+            players: String::new(),
+            teams: None,
+        }
+    }
+
+    fn update(&mut self, _: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::UpdatePlayers(players) => {
+                self.players = players;
+                true
+            }
+            Msg::UpdateTeams(teams) => {
+                self.teams = teams
+                    .and_then(|t| usize::try_from(t).ok())
+                    .filter(|t| *t > 0);
+                true
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let update_players = ctx.link().callback(Msg::UpdatePlayers);
+        let update_teams = ctx.link().callback(Msg::UpdateTeams);
+        let players: Vec<&str> = self
+            .players
+            .split(',')
+            .map(str::trim)
+            .filter(|p| !p.is_empty())
+            .collect();
+
+        let result = match seat(&players, self.teams, ctx.props().seed) {
+            Ok(seating) => {
+                let seats: Html = seating
+                    .seats
+                    .iter()
+                    .enumerate()
+                    .map(|(index, player)| {
+                        let team = match seating.teams.len() {
+                            0 => String::new(),
+                            teams => (index % teams + 1).to_string(),
+                        };
+                        html! {
+                            <tr>
+                                <th>{ index + 1 }</th>
+                                <td>{ player }</td>
+                                <td>{ team }</td>
+                            </tr>
+                        }
+                    })
+                    .collect();
+                html! {
+                    <table class="table is-hoverable is-fullwidth is-striped">
+                        <thead>
+                            <tr>
+                                <th>{ "Seat" }</th>
+                                <th>{ "Player" }</th>
+                                <th>{ "Team" }</th>
+                            </tr>
+                        </thead>
+                        <tbody>
+                            { seats }
+                        </tbody>
+                    </table>
+                }
+            }
+            Err(SeatingErrors::EmptyTeams) => html! {},
+            Err(SeatingErrors::UnevenTeams { players, teams }) => html! {
+                <p class="help is-danger">
+                    { format!("{} player(s) can't be split into {} even teams.", players, teams) }
+                </p>
+            },
+        };
+
+        html! {
+            <div class="card">
+                <div class="card-header">
+                    <label class="label card-header-title">{ "Seating" }</label>
+                </div>
+                <div class="card-content">
+                    <div class="field">
+                        <label class="label">{ "Players" }</label>
+                        <div class="control">
+                            <TextInput on_change={ update_players } value={ self.players.clone() } placeholder={ "Comma separated player names" }
+                                tooltip={
+                                    "The names of the players, separated by commas.\n\
+                                    Seats are randomized from the seed but independently of the packs.\n"
+                                }
+                            />
+                        </div>
+                    </div>
+                    <div class="field">
+                        <label class="label">{ "Teams" }</label>
+                        <div class="control">
+                            <IntegerInput min=0 on_change={ update_teams } step=1 value={ self.teams.map(|t| t as i128) } placeholder={ "Number of teams" }
+                                tooltip={
+                                    "The number of teams, leave empty for no teams.\n\
+                                    Teammates are seated alternately around the table.\n"
+                                }
+                            />
+                        </div>
+                    </div>
+                    { result }
+                </div>
+            </div>
+        }
+    }
+}
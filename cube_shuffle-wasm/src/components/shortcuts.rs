@@ -0,0 +1,66 @@
+use gloo_events::EventListener;
+use wasm_bindgen::JsCast;
+use web_sys::{HtmlInputElement, KeyboardEvent};
+use yew::prelude::*;
+
+pub enum Msg {
+    Undo,
+    Redo,
+}
+
+#[derive(PartialEq, Properties)]
+pub struct Props {
+    pub on_undo: Callback<()>,
+    pub on_redo: Callback<()>,
+}
+
+// Ctrl+Z and Ctrl+Y or Ctrl+Shift+Z anywhere on the page, except in text fields which keep their own undo.
+pub struct Shortcuts {
+    _listener: Option<EventListener>,
+}
+
+impl Component for Shortcuts {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let link = ctx.link().clone();
+        let listener = web_sys::window().map(|window| {
+            EventListener::new(&window, "keydown", move |event| {
+                let event = match event.dyn_ref::<KeyboardEvent>() {
+                    Some(e) if e.ctrl_key() || e.meta_key() => e,
+                    _ => return,
+                };
+                if event
+                    .target()
+                    .and_then(|t| t.dyn_into::<HtmlInputElement>().ok())
+                    .is_some()
+                {
+                    return;
+                }
+                let msg = match (event.key().to_lowercase().as_str(), event.shift_key()) {
+                    ("z", false) => Msg::Undo,
+                    ("z", true) | ("y", _) => Msg::Redo,
+                    _ => return,
+                };
+                event.prevent_default();
+                link.send_message(msg);
+            })
+        });
+        Self {
+            _listener: listener,
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::Undo => ctx.props().on_undo.emit(()),
+            Msg::Redo => ctx.props().on_redo.emit(()),
+        }
+        false
+    }
+
+    fn view(&self, _: &Context<Self>) -> Html {
+        html! {}
+    }
+}
@@ -0,0 +1,199 @@
+use yew::prelude::*;
+
+use cube_shuffle_core::analysis::analyze_config;
+use cube_shuffle_core::config::Config;
+use cube_shuffle_core::distribution_shuffle::Pile;
+
+#[derive(Clone, PartialEq, Properties)]
+pub struct Props {
+    pub piles: Vec<(String, Pile)>,
+    pub pack_size: usize,
+}
+
+#[derive(Debug, PartialEq)]
+struct Summary {
+    // Counted in u128, as the piles may add up to more than a usize holds on wasm32.
+    total_cards: u128,
+    pack_count: u128,
+    leftovers: u128,
+    // Cards each pile is expected to put in a pack, as leftovers are taken from all piles alike.
+    per_pack: Vec<(String, f64)>,
+    warnings: Vec<String>,
+}
+
+fn summarize(piles: &[(String, Pile)], pack_size: usize) -> Summary {
+    let total_cards: u128 = piles.iter().map(|(_, pile)| pile.cards as u128).sum();
+    let pack_count = total_cards.checked_div(pack_size as u128).unwrap_or(0);
+    let per_pack = piles
+        .iter()
+        .map(|(name, pile)| {
+            let cards = match total_cards {
+                0 => 0.0,
+                // Multiplied as floats, as the product overflows a 32 bit usize in wasm with large piles.
+                _ => pile.cards as f64 * pack_size as f64 / total_cards as f64,
+            };
+            (name.clone(), cards)
+        })
+        .collect();
+    let warnings = match pack_size {
+        0 => vec![String::from("Packs need a size of at least 1.")],
+        _ if total_cards > usize::MAX as u128 => vec![format!(
+            "Too many cards, at most {} can be shuffled.",
+            usize::MAX
+        )],
+        _ => {
+            let config = Config::new(String::new(), pack_size, piles.iter().cloned().collect());
+            analyze_config(&config)
+                .iter()
+                .map(ToString::to_string)
+                .collect()
+        }
+    };
+    Summary {
+        total_cards,
+        pack_count,
+        leftovers: total_cards
+            .checked_rem(pack_size as u128)
+            .unwrap_or(total_cards),
+        per_pack,
+        warnings,
+    }
+}
+
+#[function_component(SummaryPanel)]
+pub fn summary_panel(props: &Props) -> Html {
+    let summary = summarize(&props.piles, props.pack_size);
+    let per_pack: Html = summary
+        .per_pack
+        .iter()
+        .map(|(name, cards)| {
+            html! {
+                <tr>
+                    <th>{ name }</th>
+                    <td>{ format!("{:.2}", cards) }</td>
+                </tr>
+            }
+        })
+        .collect();
+    let warnings: Html = summary
+        .warnings
+        .iter()
+        .map(|warning| html! { <p class="help is-warning">{ warning }</p> })
+        .collect();
+    html! {
+        <div class="box">
+            <nav class="level">
+                <div class="level-item has-text-centered">
+                    <div>
+                        <p class="heading">{ "Cards" }</p>
+                        <p class="title">{ summary.total_cards }</p>
+                    </div>
+                </div>
+                <div class="level-item has-text-centered">
+                    <div>
+                        <p class="heading">{ "Packs" }</p>
+                        <p class="title">{ summary.pack_count }</p>
+                    </div>
+                </div>
+                <div class="level-item has-text-centered">
+                    <div>
+                        <p class="heading">{ "Leftovers" }</p>
+                        <p class="title">{ summary.leftovers }</p>
+                    </div>
+                </div>
+            </nav>
+            <details>
+                <summary>{ "Average cards per pack" }</summary>
+                <table class="table is-fullwidth is-striped">
+                    <tbody>
+                        { per_pack }
+                    </tbody>
+                </table>
+            </details>
+            { warnings }
+        </div>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cube_shuffle_core::distribution_shuffle::Pile;
+
+    use crate::components::summary_panel::summarize;
+
+    #[test]
+    fn sparse_pile() {
+        let piles = vec![
+            (
+                String::from("Green"),
+                Pile {
+                    cards: 345,
+                    randomness: 0.1,
+                },
+            ),
+            (
+                String::from("Lands"),
+                Pile {
+                    cards: 20,
+                    randomness: 0.0,
+                },
+            ),
+        ];
+        let summary = summarize(&piles, 15);
+        assert_eq!(365, summary.total_cards);
+        assert_eq!(24, summary.pack_count);
+        assert_eq!(5, summary.leftovers);
+        assert_eq!(
+            vec![
+                String::from(
+                    "Green is the only pile with randomness, which has no effect without a second one."
+                ),
+                String::from("Lands at 0% randomness with 20 cards < 24 packs: 4 packs get none."),
+            ],
+            summary.warnings
+        );
+        let per_pack: f64 = summary.per_pack.iter().map(|(_, cards)| cards).sum();
+        assert!((per_pack - 15.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn large_piles() {
+        let piles = vec![(
+            String::from("Green"),
+            Pile {
+                cards: usize::MAX / 2,
+                randomness: 0.0,
+            },
+        )];
+        let summary = summarize(&piles, 15);
+        assert!((summary.per_pack[0].1 - 15.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn too_many_cards() {
+        let pile = Pile {
+            cards: usize::MAX,
+            randomness: 0.0,
+        };
+        let piles = vec![(String::from("Green"), pile), (String::from("Blue"), pile)];
+        let summary = summarize(&piles, 15);
+        assert_eq!(usize::MAX as u128 * 2, summary.total_cards);
+        assert_eq!(
+            vec![format!(
+                "Too many cards, at most {} can be shuffled.",
+                usize::MAX
+            )],
+            summary.warnings
+        );
+    }
+
+    #[test]
+    fn empty() {
+        let summary = summarize(&[], 0);
+        assert_eq!(
+            (0, 0, 0),
+            (summary.total_cards, summary.pack_count, summary.leftovers)
+        );
+        assert_eq!(1, summary.warnings.len());
+    }
+}
@@ -0,0 +1,103 @@
+use cube_shuffle_core::distribution_shuffle::Pack;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Order {
+    PackFirst,
+    PileFirst,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Step {
+    pub pile: String,
+    pub pack: usize,
+    pub cards: usize,
+}
+
+// Packs which are already done are skipped, piles are dealt in name order.
+pub fn steps(packs: &[Pack<String>], done: &[bool], order: Order) -> Vec<Step> {
+    let mut steps: Vec<Step> = packs
+        .iter()
+        .enumerate()
+        .filter(|(pack, _)| !done.get(*pack).copied().unwrap_or(false))
+        .flat_map(|(pack, contents)| {
+            contents
+                .card_sources
+                .iter()
+                .filter(|(_, cards)| **cards > 0)
+                .map(move |(pile, cards)| Step {
+                    pile: pile.clone(),
+                    pack,
+                    cards: *cards,
+                })
+        })
+        .collect();
+    match order {
+        Order::PackFirst => steps.sort_by(|a, b| (a.pack, &a.pile).cmp(&(b.pack, &b.pile))),
+        Order::PileFirst => steps.sort_by(|a, b| (&a.pile, a.pack).cmp(&(&b.pile, b.pack))),
+    }
+    steps
+}
+
+// The pack which gets its last cards in the given step, if any.
+pub fn completed_pack(steps: &[Step], step: usize) -> Option<usize> {
+    let pack = steps.get(step)?.pack;
+    match steps[step + 1..].iter().any(|s| s.pack == pack) {
+        true => None,
+        false => Some(pack),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use cube_shuffle_core::distribution_shuffle::Pack;
+
+    use crate::dealing::{completed_pack, steps, Order, Step};
+
+    fn pack(sources: &[(&str, usize)]) -> Pack<String> {
+        Pack {
+            card_sources: sources
+                .iter()
+                .map(|(pile, cards)| (String::from(*pile), *cards))
+                .collect::<HashMap<String, usize>>(),
+        }
+    }
+
+    fn step(pile: &str, pack: usize, cards: usize) -> Step {
+        Step {
+            pile: String::from(pile),
+            pack,
+            cards,
+        }
+    }
+
+    #[test]
+    fn orders() {
+        let packs = vec![
+            pack(&[("Green", 2), ("Blue", 1)]),
+            pack(&[("Green", 1), ("Blue", 2)]),
+            pack(&[("Green", 3), ("Blue", 0)]),
+        ];
+        let pack_first = steps(&packs, &[false, false, false], Order::PackFirst);
+        assert_eq!(
+            vec![
+                step("Blue", 0, 1),
+                step("Green", 0, 2),
+                step("Blue", 1, 2),
+                step("Green", 1, 1),
+                step("Green", 2, 3)
+            ],
+            pack_first
+        );
+        assert_eq!(None, completed_pack(&pack_first, 0));
+        assert_eq!(Some(0), completed_pack(&pack_first, 1));
+        let pile_first = steps(&packs, &[false, true, false], Order::PileFirst);
+        assert_eq!(
+            vec![step("Blue", 0, 1), step("Green", 0, 2), step("Green", 2, 3)],
+            pile_first
+        );
+        assert_eq!(Some(0), completed_pack(&pile_first, 1));
+        assert_eq!(None, completed_pack(&pile_first, 3));
+    }
+}
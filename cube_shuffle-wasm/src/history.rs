@@ -0,0 +1,100 @@
+// Enough for a whole session of pile editing without keeping every keystroke forever.
+const LIMIT: usize = 100;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct History<T> {
+    past: Vec<T>,
+    future: Vec<T>,
+    // Changes of the same group in a row, such as typing a seed, are undone together.
+    group: Option<&'static str>,
+}
+
+impl<T> Default for History<T> {
+    fn default() -> Self {
+        Self {
+            past: Vec::new(),
+            future: Vec::new(),
+            group: None,
+        }
+    }
+}
+
+impl<T> History<T> {
+    // Records the state from before a change.
+    pub fn record(&mut self, before: T, group: Option<&'static str>) {
+        self.future.clear();
+        if group.is_some() && group == self.group {
+            return;
+        }
+        self.group = group;
+        self.past.push(before);
+        if self.past.len() > LIMIT {
+            self.past.remove(0);
+        }
+    }
+
+    pub fn undo(&mut self, current: T) -> Option<T> {
+        let previous = self.past.pop()?;
+        self.future.push(current);
+        self.group = None;
+        Some(previous)
+    }
+
+    pub fn redo(&mut self, current: T) -> Option<T> {
+        let next = self.future.pop()?;
+        self.past.push(current);
+        self.group = None;
+        Some(next)
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.past.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.future.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use crate::history::History;
+
+    #[test]
+    fn groups() {
+        let mut history = History::default();
+        history.record("", Some("seed"));
+        history.record("1", Some("seed"));
+        history.record("12", None);
+        assert_eq!(Some("12"), history.undo("12 piles"));
+        assert_eq!(Some(""), history.undo("12"));
+        assert_eq!(None, history.undo(""));
+        assert_eq!(Some("12"), history.redo(""));
+        history.record("12", Some("seed"));
+        assert!(!history.can_redo());
+    }
+
+    proptest! {
+        #[test]
+        fn undo_redo_round_trip(states in prop::collection::vec(0..1000u32, 1..150)) {
+            let mut history = History::default();
+            let mut current = 0;
+            for state in states.iter() {
+                history.record(current, None);
+                current = *state;
+            }
+            let kept = states.len().min(100);
+            for _ in 0..kept {
+                current = history.undo(current).unwrap();
+            }
+            prop_assert!(!history.can_undo());
+            for _ in 0..kept {
+                current = history.redo(current).unwrap();
+            }
+            prop_assert_eq!(*states.last().unwrap(), current);
+            prop_assert!(!history.can_redo());
+        }
+    }
+}
@@ -5,6 +5,11 @@ use std::panic;
 use crate::components::app::App;
 
 mod components;
+mod dealing;
+mod history;
+mod presets;
+mod share;
+mod storage;
 
 fn main() {
     panic::set_hook(Box::new(console_error_panic_hook::hook));
@@ -0,0 +1,120 @@
+use serde::{Deserialize, Serialize};
+
+use cube_shuffle_core::distribution_shuffle::{Odds, Pile};
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Template {
+    pub name: String,
+    pub pack_size: usize,
+    pub piles: Vec<(String, Pile)>,
+}
+
+fn template(name: &str, pack_size: usize, piles: &[(&str, usize, Odds)]) -> Template {
+    Template {
+        name: String::from(name),
+        pack_size,
+        piles: piles
+            .iter()
+            .map(|(pile, cards, randomness)| {
+                (
+                    String::from(*pile),
+                    Pile {
+                        cards: *cards,
+                        randomness: *randomness,
+                    },
+                )
+            })
+            .collect(),
+    }
+}
+
+// Every preset fills its packs without leftovers, colors get less randomness than the secondary piles.
+pub fn builtin() -> Vec<Template> {
+    vec![
+        template(
+            "MTG 360 card cube",
+            15,
+            &[
+                ("White", 50, 0.1),
+                ("Blue", 50, 0.1),
+                ("Black", 50, 0.1),
+                ("Red", 50, 0.1),
+                ("Green", 50, 0.1),
+                ("Multicolored", 50, 0.3),
+                ("Nonbasic lands", 30, 0.3),
+                ("Colorless", 30, 0.3),
+            ],
+        ),
+        template(
+            "MTG 540 card cube",
+            15,
+            &[
+                ("White", 75, 0.1),
+                ("Blue", 75, 0.1),
+                ("Black", 75, 0.1),
+                ("Red", 75, 0.1),
+                ("Green", 75, 0.1),
+                ("Multicolored", 75, 0.3),
+                ("Nonbasic lands", 45, 0.3),
+                ("Colorless", 45, 0.3),
+            ],
+        ),
+        template(
+            "MTG Pauper cube",
+            15,
+            &[
+                ("White", 60, 0.1),
+                ("Blue", 60, 0.1),
+                ("Black", 60, 0.1),
+                ("Red", 60, 0.1),
+                ("Green", 60, 0.1),
+                ("Multicolored", 40, 0.3),
+                ("Nonbasic lands", 60, 0.3),
+                ("Colorless", 50, 0.3),
+            ],
+        ),
+        template(
+            "Pokémon style cube",
+            10,
+            &[
+                ("Pokémon", 130, 0.1),
+                ("Trainers", 80, 0.2),
+                ("Energy", 30, 0.0),
+            ],
+        ),
+        template(
+            "Lorcana style cube",
+            12,
+            &[
+                ("Amber", 48, 0.1),
+                ("Amethyst", 48, 0.1),
+                ("Emerald", 48, 0.1),
+                ("Ruby", 48, 0.1),
+                ("Sapphire", 48, 0.1),
+                ("Steel", 48, 0.1),
+            ],
+        ),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use cube_shuffle_core::config::Config;
+
+    use crate::presets::builtin;
+
+    #[test]
+    fn builtin_presets_deal_evenly() {
+        for preset in builtin() {
+            let cards: usize = preset.piles.iter().map(|(_, pile)| pile.cards).sum();
+            assert_eq!(0, cards % preset.pack_size, "{}", preset.name);
+            let config = Config::new(
+                String::from("1"),
+                preset.pack_size,
+                preset.piles.into_iter().collect(),
+            );
+            assert_eq!(Ok(()), config.validate());
+            assert_eq!(cards / config.pack_size, config.shuffle().unwrap().len());
+        }
+    }
+}
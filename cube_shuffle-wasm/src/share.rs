@@ -0,0 +1,108 @@
+use cube_shuffle_core::distribution_shuffle::{Odds, Pile};
+
+const PREFIX: &str = "config=";
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Shared {
+    pub seed: String,
+    pub pack_size: usize,
+    pub piles: Vec<(String, Pile)>,
+    pub shuffled: bool,
+}
+
+// A JSON array keeps the link short and the piles in the order they are listed in.
+type Compact = (String, usize, Vec<(String, usize, Odds)>, bool);
+
+pub fn encode(shared: &Shared) -> String {
+    let compact: Compact = (
+        shared.seed.clone(),
+        shared.pack_size,
+        shared
+            .piles
+            .iter()
+            .map(|(name, pile)| (name.clone(), pile.cards, pile.randomness))
+            .collect(),
+        shared.shuffled,
+    );
+    let json = serde_json::to_string(&compact).unwrap();
+    format!(
+        "{}{}",
+        PREFIX,
+        base64::encode_config(json, base64::URL_SAFE_NO_PAD)
+    )
+}
+
+// Returns None for fragments which are not shared configs at all.
+pub fn decode(fragment: &str) -> Option<Result<Shared, String>> {
+    let encoded = fragment.trim_start_matches('#').strip_prefix(PREFIX)?;
+    let broken = |e: String| format!("The shared link is broken: {}", e);
+    Some(
+        base64::decode_config(encoded, base64::URL_SAFE_NO_PAD)
+            .map_err(|e| broken(e.to_string()))
+            .and_then(|json| {
+                serde_json::from_slice::<Compact>(&json).map_err(|e| broken(e.to_string()))
+            })
+            .and_then(|(seed, pack_size, piles, shuffled)| {
+                let piles = piles
+                    .into_iter()
+                    .map(
+                        |(name, cards, randomness)| match (0.0..=1.0).contains(&randomness) {
+                            true => Ok((name, Pile { cards, randomness })),
+                            false => Err(broken(format!("invalid randomness of {}", name))),
+                        },
+                    )
+                    .collect::<Result<_, _>>()?;
+                Ok(Shared {
+                    seed,
+                    pack_size,
+                    piles,
+                    shuffled,
+                })
+            }),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use cube_shuffle_core::distribution_shuffle::Pile;
+
+    use crate::share::{decode, encode, Shared};
+
+    #[test]
+    fn round_trip() {
+        let shared = Shared {
+            seed: String::from("Friday night"),
+            pack_size: 15,
+            piles: vec![
+                (
+                    String::from("Green"),
+                    Pile {
+                        cards: 50,
+                        randomness: 0.1,
+                    },
+                ),
+                (
+                    String::from("Blue & Black"),
+                    Pile {
+                        cards: 45,
+                        randomness: 0.0,
+                    },
+                ),
+            ],
+            shuffled: true,
+        };
+        let fragment = format!("#{}", encode(&shared));
+        assert!(fragment
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "#=-_".contains(c)));
+        assert_eq!(Some(Ok(shared)), decode(&fragment));
+    }
+
+    #[test]
+    fn malformed() {
+        assert_eq!(None, decode(""));
+        assert_eq!(None, decode("#top"));
+        assert!(matches!(decode("#config=!!"), Some(Err(_))));
+        assert!(matches!(decode("#config=W10"), Some(Err(_))));
+    }
+}
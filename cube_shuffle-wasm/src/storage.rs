@@ -0,0 +1,59 @@
+use gloo_storage::{LocalStorage, Storage};
+use serde::{Deserialize, Serialize};
+
+use cube_shuffle_core::config::Config;
+
+use crate::presets::Template;
+
+const KEY: &str = "cube_shuffle.config";
+const TEMPLATES_KEY: &str = "cube_shuffle.templates";
+// Bump when the stored layout changes, saved data of other versions is then ignored instead of misread.
+const SCHEMA: u32 = 1;
+
+#[derive(Serialize, Deserialize)]
+pub struct Saved {
+    schema: u32,
+    pub config: Config,
+    // The order piles are listed in, as the config does not keep any order.
+    #[serde(default)]
+    pub order: Vec<String>,
+    // Which packs are dealt, only ever saved together with the config they were shuffled from.
+    #[serde(default)]
+    pub done: Option<Vec<bool>>,
+    #[serde(default)]
+    pub shuffled: bool,
+}
+
+pub fn load() -> Option<Saved> {
+    LocalStorage::get::<Saved>(KEY)
+        .ok()
+        .filter(|saved| saved.schema == SCHEMA)
+}
+
+pub fn save(config: &Config, order: Vec<String>, done: Option<Vec<bool>>, shuffled: bool) {
+    let saved = Saved {
+        schema: SCHEMA,
+        config: config.clone(),
+        order,
+        done,
+        shuffled,
+    };
+    if let Err(e) = LocalStorage::set(KEY, saved) {
+        web_sys::console::warn_1(&format!("Saving the config failed: {}", e).into());
+    }
+}
+
+pub fn clear() {
+    LocalStorage::delete(KEY);
+}
+
+// Templates are kept apart from the config so resetting the app does not remove them.
+pub fn load_templates() -> Vec<Template> {
+    LocalStorage::get(TEMPLATES_KEY).unwrap_or_default()
+}
+
+pub fn save_templates(templates: &[Template]) {
+    if let Err(e) = LocalStorage::set(TEMPLATES_KEY, templates) {
+        web_sys::console::warn_1(&format!("Saving the templates failed: {}", e).into());
+    }
+}